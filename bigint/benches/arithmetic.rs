@@ -0,0 +1,58 @@
+#![feature(test)]
+
+extern crate test;
+
+use bigint::BigInt;
+use test::{black_box, Bencher};
+
+// A value with the given number of limbs, all of their bits set.
+fn value(limbs: usize) -> BigInt {
+    (BigInt::one() << (64 * limbs)) - 1u8
+}
+
+#[bench]
+fn step_range(b: &mut Bencher) {
+    b.iter(|| (BigInt::zero()..BigInt::from(1000)).fold(BigInt::zero(), |acc, i| acc + i));
+}
+
+#[bench]
+fn add_small(b: &mut Bencher) {
+    let (x, y) = (BigInt::from(123_456_789), BigInt::from(987_654_321));
+    b.iter(|| black_box(&x) + black_box(&y));
+}
+
+#[bench]
+fn mul_small(b: &mut Bencher) {
+    let (x, y) = (BigInt::from(123_456_789), BigInt::from(987_654_321));
+    b.iter(|| black_box(&x) * black_box(&y));
+}
+
+#[bench]
+fn div_small(b: &mut Bencher) {
+    let (x, y) = (value(2), BigInt::from(987_654_321));
+    b.iter(|| black_box(&x) / black_box(&y));
+}
+
+#[bench]
+fn cmp_small(b: &mut Bencher) {
+    let (x, y) = (BigInt::from(123_456_789), BigInt::from(987_654_321));
+    b.iter(|| black_box(&x) < black_box(&y));
+}
+
+#[bench]
+fn add_large(b: &mut Bencher) {
+    let (x, y) = (value(1000), value(999));
+    b.iter(|| black_box(&x) + black_box(&y));
+}
+
+#[bench]
+fn mul_large(b: &mut Bencher) {
+    let (x, y) = (value(1000), value(999));
+    b.iter(|| black_box(&x) * black_box(&y));
+}
+
+#[bench]
+fn div_large(b: &mut Bencher) {
+    let (x, y) = (value(2000), value(999));
+    b.iter(|| black_box(&x) / black_box(&y));
+}
@@ -1,10 +1,14 @@
 pub mod add;
 pub mod div;
 pub mod modulo;
+pub(crate) mod montgomery;
 pub mod mul;
 pub mod rem;
+pub mod root;
 pub mod sub;
 
+use std::ops::Neg;
+
 use crate::BigInt;
 
 impl BigInt {
@@ -17,6 +21,23 @@ impl BigInt {
     }
 }
 
+impl Neg for BigInt {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        self.signed = !self.signed && self != 0;
+        self
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -27,4 +48,12 @@ mod tests {
         assert_eq!(BigInt::from(-256_i16).abs(), BigInt::from(256_u16));
         assert_eq!(BigInt::from(-65536_i32).abs(), BigInt::from(65536_u32));
     }
+
+    #[test]
+    fn neg() {
+        assert_eq!(-BigInt::from(5), -5);
+        assert_eq!(-BigInt::from(-5), 5);
+        assert_eq!(-&BigInt::from(u128::MAX), BigInt::from(u128::MAX) * -1);
+        assert!(!(-BigInt::zero()).is_negative());
+    }
 }
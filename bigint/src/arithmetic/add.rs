@@ -1,7 +1,8 @@
+use std::iter::Sum;
 use std::mem;
 use std::ops::{Add, AddAssign};
 
-use crate::BigInt;
+use crate::{Base, BigInt};
 
 impl Add<Self> for BigInt {
     type Output = Self;
@@ -21,19 +22,20 @@ impl AddAssign<Self> for BigInt {
             other.signed = false;
             *self -= other;
         } else {
-            if self.abs() < other.abs() {
+            if self.data.len() < other.data.len() {
                 mem::swap(self, &mut other);
             }
 
             let mut carry = false;
+            let (a, b) = (&mut *self.data, &*other.data);
             let mut i = 0;
 
-            while i < other.data.len() {
-                (self.data[i], carry) = self.data[i].carrying_add(other.data[i], carry);
+            while i < b.len() {
+                (a[i], carry) = a[i].carrying_add(b[i], carry);
                 i += 1;
             }
-            while i < self.data.len() {
-                (self.data[i], carry) = self.data[i].carrying_add(0, carry);
+            while carry && i < a.len() {
+                (a[i], carry) = a[i].carrying_add(0, carry);
                 i += 1;
             }
 
@@ -44,6 +46,28 @@ impl AddAssign<Self> for BigInt {
     }
 }
 
+// Adds `b`, shifted up by `offset` limbs, into `a`, growing `a` as needed.
+pub(crate) fn add_limbs(a: &mut Vec<Base>, b: &[Base], offset: usize) {
+    if a.len() < offset + b.len() {
+        a.resize(offset + b.len(), 0);
+    }
+
+    let mut carry = false;
+    let mut i = offset;
+
+    for &x in b {
+        (a[i], carry) = a[i].carrying_add(x, carry);
+        i += 1;
+    }
+    while carry {
+        if i == a.len() {
+            a.push(0);
+        }
+        (a[i], carry) = a[i].carrying_add(0, carry);
+        i += 1;
+    }
+}
+
 impl Add<&Self> for BigInt {
     type Output = Self;
 
@@ -97,10 +121,22 @@ macro_rules! impl_primitave_add {
 
 impl_primitave_add!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
 
+impl Sum for BigInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(BigInt::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a> Sum<&'a BigInt> for BigInt {
+    fn sum<I: Iterator<Item = &'a BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::zero(), |acc, x| acc + x)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Base, BaseExt};
+    use crate::BaseExt;
 
     macro_rules! test_add {
         ($name:ident: $a:expr, $b:expr, $e:expr) => {
@@ -128,7 +164,7 @@ mod tests {
         BigInt::from(BaseExt::MAX),
         BigInt {
             signed: false,
-            data: vec![Base::MAX - 1, Base::MAX, 1]
+            data: [Base::MAX - 1, Base::MAX, 1].into()
         }
     );
 
@@ -149,15 +185,23 @@ mod tests {
             big_inv: BigInt::from(Base::MAX),
             BigInt {
                 signed: true,
-                data: vec![Base::MAX]
+                data: [Base::MAX].into()
             },
             0
         );
 
         test_add!(big_neg_big:
             BigInt::from(Base::MAX - 1),
-            BigInt { signed: true, data: vec![(Base::MAX - 1) / 2] },
+            BigInt { signed: true, data: [(Base::MAX - 1) / 2].into() },
             (Base::MAX - 1) / 2
         );
     }
+
+    #[test]
+    fn sum() {
+        let xs = (-50..100).map(BigInt::from).collect::<Vec<_>>();
+        assert_eq!(xs.iter().sum::<BigInt>(), (-50..100).sum::<i32>());
+        assert_eq!(xs.into_iter().sum::<BigInt>(), (-50..100).sum::<i32>());
+        assert_eq!(std::iter::empty::<BigInt>().sum::<BigInt>(), 0);
+    }
 }
@@ -1,41 +1,266 @@
 use std::ops::{Div, DivAssign};
 
-use crate::BigInt;
+use crate::cmp::cmp_limbs;
+use crate::{Base, BaseExt, BigInt};
 
 impl BigInt {
-    pub(crate) fn div_rem(mut self, other: Self) -> (BigInt, BigInt) {
+    pub(crate) fn div_rem(self, other: Self) -> (BigInt, BigInt) {
         assert!(other != 0, "attempt to divide by zero");
-        let mut rem = BigInt::zero();
-
-        if self.abs() < other.abs() {
-            rem = self.clone();
-            self = BigInt::zero();
-        } else if self.abs() == other.abs() {
-            self.signed = self.signed ^ other.signed;
-            self.data = vec![1];
-        } else if other.abs() != 1 {
-            let mut quot = BigInt::zero();
-
-            for i in (0..(self.bits())).rev() {
-                rem <<= 1;
-                rem |= (self.clone() >> i) & 1;
-
-                if rem >= other.abs() {
-                    rem -= other.abs();
-                    quot |= BigInt::one() << i;
-                }
+
+        let (mut quot, mut rem) = if other.data.len() == 1 {
+            let mut quot = self.abs();
+            let rem = quot.div_rem_limb(other.data[0]);
+            (quot, BigInt::from(rem))
+        } else {
+            let (quot, rem) = div_rem_limbs(&self.data, &other.data);
+            (BigInt::from_limbs(quot), BigInt::from_limbs(rem))
+        };
+
+        quot.signed = (self.signed ^ other.signed) && quot != 0;
+        rem.signed = self.signed && rem != 0;
+        (quot, rem)
+    }
+
+    // Divides the magnitude of `self` by a single limb in place, returning the remainder.
+    pub(crate) fn div_rem_limb(&mut self, d: Base) -> Base {
+        let mut rem: BaseExt = 0;
+
+        for x in self.data.iter_mut().rev() {
+            let n = rem << Base::BITS | *x as BaseExt;
+            *x = (n / d as BaseExt) as Base;
+            rem = n % d as BaseExt;
+        }
+
+        self.trim();
+        rem as Base
+    }
+
+    // The remainder of dividing the magnitude of `self` by a single limb.
+    pub(crate) fn rem_limb(&self, d: Base) -> Base {
+        self.data.iter().rev().fold(0, |rem: BaseExt, &x| {
+            (rem << Base::BITS | x as BaseExt) % d as BaseExt
+        }) as Base
+    }
+}
+
+// Divisors with fewer limbs than this, or which would leave a quotient with fewer limbs than this,
+// are divided with Knuth's algorithm D.
+const BURNIKEL_ZIEGLER_THRESHOLD: usize = 160;
+
+/// Divides the magnitude `a` by the magnitude `b`, returning the quotient and remainder limbs.
+/// Neither may have leading zero limbs, and `b` must not be zero.
+pub(crate) fn div_rem_limbs(a: &[Base], b: &[Base]) -> (Vec<Base>, Vec<Base>) {
+    if b.len() >= BURNIKEL_ZIEGLER_THRESHOLD
+        && a.len() >= b.len() + BURNIKEL_ZIEGLER_THRESHOLD
+    {
+        burnikel_ziegler(a, b)
+    } else {
+        long_division(a, b)
+    }
+}
+
+// Handles the trivial cases before falling back to Knuth's algorithm D.
+fn long_division(a: &[Base], b: &[Base]) -> (Vec<Base>, Vec<Base>) {
+    if cmp_limbs(a, b).is_lt() {
+        (vec![0], a.to_vec())
+    } else if b.len() == 1 {
+        let mut quot = BigInt::from_limbs(a.to_vec());
+        let rem = quot.div_rem_limb(b[0]);
+        (quot.data.into(), vec![rem])
+    } else {
+        knuth_d(a, b)
+    }
+}
+
+// Shifts `x` left by `s < Base::BITS` bits, adding one limb to hold the overflow.
+fn shl_bits(x: &[Base], s: u32) -> Vec<Base> {
+    let mut carry = 0;
+    let mut out = Vec::with_capacity(x.len() + 1);
+
+    for &e in x {
+        out.push(e << s | carry);
+        carry = e.checked_shr(Base::BITS - s).unwrap_or(0);
+    }
+    out.push(carry);
+    out
+}
+
+// Shifts `x` right by `s < Base::BITS` bits.
+fn shr_bits(x: &[Base], s: u32) -> Vec<Base> {
+    let mut out = Vec::with_capacity(x.len());
+
+    for i in 0..x.len() {
+        let carry = x.get(i + 1).unwrap_or(&0).checked_shl(Base::BITS - s);
+        out.push(x[i] >> s | carry.unwrap_or(0));
+    }
+    out
+}
+
+// Normalized long division from Knuth's "The Art of Computer Programming", vol. 2, section
+// 4.3.1, algorithm D, O(n * m).
+//
+// Both operands are shifted so the divisor's top bit is set, which guarantees that the quotient
+// digit estimated from the top two limbs of the running remainder is at most two too large.
+fn knuth_d(a: &[Base], b: &[Base]) -> (Vec<Base>, Vec<Base>) {
+    const BITS: u32 = Base::BITS;
+    const MAX: BaseExt = Base::MAX as BaseExt;
+
+    let s = b[b.len() - 1].leading_zeros();
+    let mut v = shl_bits(b, s);
+    v.pop();
+    let mut u = shl_bits(a, s);
+
+    let n = v.len();
+    let mut quot = vec![0; u.len() - n];
+    let (v1, v2) = (v[n - 1] as BaseExt, v[n - 2] as BaseExt);
+
+    for j in (0..quot.len()).rev() {
+        let top = (u[j + n] as BaseExt) << BITS | u[j + n - 1] as BaseExt;
+        let (mut qhat, mut rhat) = (top / v1, top % v1);
+
+        while qhat > MAX || qhat * v2 > (rhat << BITS | u[j + n - 2] as BaseExt) {
+            qhat -= 1;
+            rhat += v1;
+            if rhat > MAX {
+                break;
             }
-            while self.data.ends_with(&[0]) && self.data.len() > 1 {
-                self.data.pop();
+        }
+
+        // subtract `qhat * v` from the window of the remainder
+        let (mut carry, mut borrow) = (0, false);
+        for i in 0..n {
+            let lo;
+            (lo, carry) = (qhat as Base).carrying_mul(v[i], carry);
+            (u[j + i], borrow) = u[j + i].borrowing_sub(lo, borrow);
+        }
+        (u[j + n], borrow) = u[j + n].borrowing_sub(carry, borrow);
+
+        // the estimate was still one too large, so add a divisor back
+        if borrow {
+            qhat -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                (u[j + i], carry) = u[j + i].carrying_add(v[i], carry);
             }
+            u[j + n] = u[j + n].wrapping_add(carry as Base);
+        }
+
+        quot[j] = qhat as Base;
+    }
 
-            rem.signed = self.signed;
-            self.signed = self.signed ^ other.signed;
-            self.data = quot.data;
+    u.truncate(n);
+    let rem = shr_bits(&u, s);
+    (
+        BigInt::from_limbs(quot).data.into(),
+        BigInt::from_limbs(rem).data.into(),
+    )
+}
+
+// Recursive division from Burnikel and Ziegler, "Fast Recursive Division", O(M(n) log n).
+//
+// The divisor is shifted up to a block size of `n = j * 2^k` limbs with its top bit set, and the
+// dividend is consumed from the top two blocks at a time, each step being a `2n / n` division
+// which recurses through two `3n / 2n` divisions of half the size.
+fn burnikel_ziegler(a: &[Base], b: &[Base]) -> (Vec<Base>, Vec<Base>) {
+    const BITS: usize = Base::BITS as usize;
+
+    let m = (b.len() / BURNIKEL_ZIEGLER_THRESHOLD + 1).next_power_of_two();
+    let j = (b.len() + m - 1) / m;
+    let n = j * m;
+
+    let b = BigInt::from_limbs(b.to_vec());
+    let sigma = n * BITS - b.bits();
+    let b = shl(&b, sigma);
+    let a = shl(&BigInt::from_limbs(a.to_vec()), sigma);
+
+    // the top block must have a clear top bit so it is below `b`
+    let t = ((a.bits() + 1 + n * BITS - 1) / (n * BITS)).max(2);
+
+    let mut quot = vec![0; (t - 1) * n];
+    let mut z = limbs(&a, (t - 2) * n, t * n);
+    let mut rem = BigInt::zero();
+
+    for i in (0..(t - 1)).rev() {
+        let (q, r) = div_2n_1n(&z, &b, n);
+        quot[(i * n)..][..q.data.len()].copy_from_slice(&q.data);
+
+        if i > 0 {
+            z = shl_limbs(r, n) + limbs(&a, (i - 1) * n, i * n);
+        } else {
+            rem = r;
         }
+    }
 
-        (self, rem)
+    (
+        BigInt::from_limbs(quot).data.into(),
+        shr(&rem, sigma).data.into(),
+    )
+}
+
+// Divides `a < b * B^n` by the `n` limb `b`.
+fn div_2n_1n(a: &BigInt, b: &BigInt, n: usize) -> (BigInt, BigInt) {
+    if n % 2 == 1 || n < BURNIKEL_ZIEGLER_THRESHOLD {
+        let (q, r) = long_division(&a.data, &b.data);
+        return (BigInt::from_limbs(q), BigInt::from_limbs(r));
+    }
+
+    let half = n / 2;
+    let (q1, r) = div_3n_2n(&limbs(a, half, 4 * half), b, half);
+    let (q2, r) = div_3n_2n(&(shl_limbs(r, half) + limbs(a, 0, half)), b, half);
+
+    (shl_limbs(q1, half) + q2, r)
+}
+
+// Divides the `3 * half` limb `a < b * B^half` by the `2 * half` limb `b`.
+fn div_3n_2n(a: &BigInt, b: &BigInt, half: usize) -> (BigInt, BigInt) {
+    let a1 = limbs(a, 2 * half, 3 * half);
+    let a12 = limbs(a, half, 3 * half);
+    let b1 = limbs(b, half, 2 * half);
+    let b2 = limbs(b, 0, half);
+
+    let (mut q, r1) = if a1 < b1 {
+        div_2n_1n(&a12, &b1, half)
+    } else {
+        // the quotient saturates at `B^half - 1`
+        let q = BigInt::from_limbs(vec![Base::MAX; half]);
+        let r1 = a12 - shl_limbs(b1.clone(), half) + b1;
+        (q, r1)
+    };
+
+    let mut r = shl_limbs(r1, half) + limbs(a, 0, half) - (&q * &b2);
+    while r.is_negative() {
+        q -= 1;
+        r += b;
     }
+
+    (q, r)
+}
+
+// The value of the limbs of `x` in `lo..hi`.
+fn limbs(x: &BigInt, lo: usize, hi: usize) -> BigInt {
+    let len = x.data.len();
+    BigInt::from_limbs(x.data[lo.min(len)..hi.min(len)].to_vec())
+}
+
+// Multiplies `x` by `B^n`.
+fn shl_limbs(mut x: BigInt, n: usize) -> BigInt {
+    if x != 0 {
+        x.data.prepend_zeros(n);
+    }
+    x
+}
+
+// Shifts the magnitude `x` left by `s` bits.
+fn shl(x: &BigInt, s: usize) -> BigInt {
+    const BITS: usize = Base::BITS as usize;
+    shl_limbs(BigInt::from_limbs(shl_bits(&x.data, (s % BITS) as u32)), s / BITS)
+}
+
+// Shifts the magnitude `x` right by `s` bits.
+fn shr(x: &BigInt, s: usize) -> BigInt {
+    const BITS: usize = Base::BITS as usize;
+    let x = &x.data[(s / BITS).min(x.data.len())..];
+    BigInt::from_limbs(shr_bits(x, (s % BITS) as u32))
 }
 
 impl Div for BigInt {
@@ -109,7 +334,6 @@ impl_primitive_div!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isi
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Base, BaseExt};
 
     macro_rules! test_div {
         ($name:ident: $a:expr, $b:expr, $e:expr) => {
@@ -150,7 +374,7 @@ mod tests {
     );
 
     test_div!(bigger:
-        BigInt { signed: false, data: vec![1, 0, Base::MAX - 1, Base::MAX] },
+        BigInt { signed: false, data: [1, 0, Base::MAX - 1, Base::MAX].into() },
         BigInt::from(BaseExt::MAX),
         BigInt::from(BaseExt::MAX)
     );
@@ -160,4 +384,85 @@ mod tests {
     fn divide_by_zero() {
         _ = BigInt::one() / BigInt::zero();
     }
+
+    mod algorithms {
+        use super::*;
+        use crate::arithmetic::mul::mul_limbs;
+
+        // xorshift, so the operands are reproducible without a dependency on an rng
+        fn limbs(len: usize, seed: &mut u64) -> Vec<Base> {
+            let mut v = (0..len)
+                .map(|_| {
+                    *seed ^= *seed << 13;
+                    *seed ^= *seed >> 7;
+                    *seed ^= *seed << 17;
+                    *seed as Base
+                })
+                .collect::<Vec<_>>();
+            *v.last_mut().unwrap() |= 1;
+            v
+        }
+
+        fn check(a: &[Base], b: &[Base], (q, r): (Vec<Base>, Vec<Base>)) {
+            let a = BigInt::from_limbs(a.to_vec());
+            let b = BigInt::from_limbs(b.to_vec());
+            let q = BigInt::from_limbs(q);
+            let r = BigInt::from_limbs(r);
+
+            assert!(r < b, "remainder not below the divisor");
+            assert_eq!(q * &b + r, a);
+        }
+
+        macro_rules! test_algorithms {
+            ($name:ident: $a:expr, $b:expr) => {
+                #[test]
+                fn $name() {
+                    let mut seed = 0x2545_f491_4f6c_dd1d;
+                    let a = limbs($a, &mut seed);
+                    let b = limbs($b, &mut seed);
+
+                    let knuth = knuth_d(&a, &b);
+                    check(&a, &b, knuth.clone());
+                    assert_eq!(burnikel_ziegler(&a, &b), knuth, "burnikel-ziegler");
+                    assert_eq!(div_rem_limbs(&a, &b), knuth, "dispatch");
+                }
+            };
+        }
+
+        test_algorithms!(two_limbs: 2, 2);
+
+        test_algorithms!(small: 7, 3);
+
+        test_algorithms!(threshold: 2 * BURNIKEL_ZIEGLER_THRESHOLD, BURNIKEL_ZIEGLER_THRESHOLD);
+
+        test_algorithms!(odd_lengths: 301, 97);
+
+        test_algorithms!(short_quotient: 410, 400);
+
+        test_algorithms!(long_quotient: 1200, 90);
+
+        test_algorithms!(large: 1500, 700);
+
+        #[test]
+        fn add_back() {
+            // the quotient estimate from the top limbs is one too large for these
+            let a = [0, 0, 1 << (Base::BITS - 1), (1 << (Base::BITS - 1)) - 1];
+            let b = [1, 0, 1 << (Base::BITS - 1)];
+            check(&a, &b, knuth_d(&a, &b));
+        }
+
+        #[test]
+        fn near_multiples() {
+            let mut seed = 0x9e37_79b9_7f4a_7c15;
+            let b = vec![Base::MAX; 200];
+            let q = limbs(250, &mut seed);
+            let mut a = BigInt::from_limbs(mul_limbs(&b, &q));
+
+            for _ in 0..3 {
+                check(&a.data, &b, knuth_d(&a.data, &b));
+                check(&a.data, &b, burnikel_ziegler(&a.data, &b));
+                a -= 1;
+            }
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use crate::arithmetic::montgomery::Montgomery;
+use crate::arithmetic::mul::sliding_window;
 use crate::BigInt;
 
 impl BigInt {
@@ -10,37 +12,28 @@ impl BigInt {
         rem
     }
 
-    /// Calculates the power of a number within a modulus.
-    // #[cfg(num)]
+    /// Calculates the power of a number within a modulus. The result is always in the range
+    /// `0..m.abs()`. Panics if `m` is zero or `exp` is negative.
     pub fn modpow(&self, exp: &BigInt, m: &BigInt) -> BigInt {
-        BigInt::from_le_bytes(
-            &crate::to_num_bigint(self)
-                .modpow(&crate::to_num_bigint(exp), &crate::to_num_bigint(m))
-                .to_bytes_le()
-                .1,
-        )
+        assert!(*m != 0, "attempt to calculate the remainder with a divisor of zero");
+        assert!(!exp.is_negative(), "attempt to raise to a negative power");
+
+        let m = m.abs();
+        if m == 1 {
+            BigInt::zero()
+        } else if m.is_odd() {
+            let mont = Montgomery::new(&m);
+            let x = sliding_window(mont.encode(self), exp, mont.one(), |a, b| mont.mul(a, b));
+            mont.decode(&x)
+        } else {
+            sliding_window(self.modulo(&m), exp, BigInt::one(), |a, b| (a * b) % &m)
+        }
     }
-    // pub fn mod_pow(&self, exp: &BigInt, m: &BigInt) -> BigInt {
-    //     if exp == 0 {
-    //         return BigInt::one().modulo(m);
-    //     }
-
-    //     let base = self.clone();
-    //     let mut val = self.clone();
-    //     let mut exp = exp.clone();
-
-    //     while exp > 0 {
-    //         val = val.modulo(m) * base.modulo(m);
-    //         exp -= 1;
-    //     }
-    //     val.modulo(m)
-    // }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Base, BaseExt};
 
     macro_rules! test_mod {
         ($name:ident: $a:expr, $b:expr, $e:expr) => {
@@ -58,4 +51,66 @@ mod tests {
     test_mod!(neg_denominator: BigInt::from(7), BigInt::from(-3), 1);
 
     test_mod!(negative: BigInt::from(-7), BigInt::from(-3), 2);
+
+    macro_rules! test_modpow {
+        ($name:ident: $a:expr, $b:expr, $m:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!($a.modpow(&$b, &$m), $e);
+            }
+        };
+    }
+
+    test_modpow!(zero_exp: BigInt::from(7), BigInt::zero(), BigInt::from(13), 1);
+
+    test_modpow!(mod_one: BigInt::from(7), BigInt::from(3), BigInt::one(), 0);
+
+    test_modpow!(small: BigInt::from(4), BigInt::from(13), BigInt::from(497), 445);
+
+    test_modpow!(even_modulus: BigInt::from(3), BigInt::from(200), BigInt::from(1000), 1);
+
+    test_modpow!(neg_base: BigInt::from(-2), BigInt::from(3), BigInt::from(5), 2);
+
+    test_modpow!(neg_modulus: BigInt::from(2), BigInt::from(3), BigInt::from(-5), 3);
+
+    test_modpow!(fermat:
+        BigInt::from(3),
+        (BigInt::one() << 127u32) - 2,
+        (BigInt::one() << 127u32) - 1,
+        1
+    );
+
+    #[test]
+    fn matches_repeated_multiplication() {
+        let a = BigInt::from(0x0123_4567_89ab_cdef_fedc_ba98_7654_3210_u128);
+        let odd = BigInt::from(u128::MAX - 158) * 0x1234_5677_u32;
+        let even = odd.clone() + 1;
+
+        for m in [odd, even] {
+            let mut e = BigInt::one();
+            for i in 1..40 {
+                e = (e * &a) % &m;
+                assert_eq!(a.modpow(&BigInt::from(i), &m), e);
+            }
+        }
+    }
+
+    #[test]
+    fn exponent_sum() {
+        let a = BigInt::from(0xfedc_ba98_7654_3210_u64) << 300u32;
+        let m = (BigInt::one() << 521u32) - 1;
+        let x = BigInt::from(0x1234_5678_9abc_def0_u64) << 200u32;
+        let y = BigInt::from(0x0fed_cba9_8765_4321_u64) << 100u32;
+
+        assert_eq!(
+            a.modpow(&(&x + &y), &m),
+            (a.modpow(&x, &m) * a.modpow(&y, &m)) % &m
+        );
+    }
+
+    #[test]
+    #[should_panic = "attempt to raise to a negative power"]
+    fn negative_exponent() {
+        _ = BigInt::from(2).modpow(&BigInt::from(-1), &BigInt::from(5));
+    }
 }
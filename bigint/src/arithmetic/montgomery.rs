@@ -0,0 +1,161 @@
+//! Montgomery representation of residues, which replaces the division in a
+//! modular multiplication with shifts and a single conditional subtraction.
+
+use crate::arithmetic::sub::sub_limbs;
+use crate::constant_time::{select_limbs, Choice};
+use crate::{Base, BigInt};
+
+/// Precomputed values for multiplying residues of an odd modulus `n`, where
+/// `R = 2^(Base::BITS * n.len())`.
+pub(crate) struct Montgomery {
+    n: Vec<Base>,
+    // `-n^-1 mod 2^Base::BITS`
+    n_inv: Base,
+    // `R^2 mod n`, used to move values into Montgomery form
+    r2: Vec<Base>,
+}
+
+impl Montgomery {
+    /// Prepares a context for the odd modulus `n`.
+    pub(crate) fn new(n: &BigInt) -> Self {
+        assert!(n.is_odd(), "montgomery modulus must be odd");
+
+        let n = n.abs();
+        let len = n.data.len();
+
+        // newton iteration doubles the number of correct low bits each step
+        let mut inv: Base = 1;
+        for _ in 0..Base::BITS.trailing_zeros() {
+            inv = inv.wrapping_mul((2 as Base).wrapping_sub(n.data[0].wrapping_mul(inv)));
+        }
+
+        let r2 = (BigInt::one() << (2 * len * Base::BITS as usize)) % &n;
+
+        Self {
+            n_inv: inv.wrapping_neg(),
+            r2: Self::pad(r2.data.into(), len),
+            n: n.data.into(),
+        }
+    }
+
+    /// The number of limbs in every residue.
+    pub(crate) fn len(&self) -> usize {
+        self.n.len()
+    }
+
+    /// The limbs of the modulus.
+    pub(crate) fn modulus(&self) -> &[Base] {
+        &self.n
+    }
+
+    fn pad(mut data: Vec<Base>, len: usize) -> Vec<Base> {
+        data.resize(len, 0);
+        data
+    }
+
+    /// Converts `x` into Montgomery form, `x * R mod n`.
+    pub(crate) fn encode(&self, x: &BigInt) -> Vec<Base> {
+        let x = x.modulo(&BigInt::from_limbs(self.n.clone()));
+        self.encode_limbs(&x.data)
+    }
+
+    /// Converts `x`, which must have no more limbs than the modulus, into Montgomery form
+    /// without first reducing it. Since `x < R` and `R^2 mod n < n`, the product is still
+    /// small enough for a single reduction.
+    pub(crate) fn encode_limbs(&self, x: &[Base]) -> Vec<Base> {
+        self.mul(&Self::pad(x.to_vec(), self.len()), &self.r2)
+    }
+
+    /// Converts `x` out of Montgomery form, `x * R^-1 mod n`.
+    pub(crate) fn decode(&self, x: &[Base]) -> BigInt {
+        let mut one = vec![0; self.len()];
+        one[0] = 1;
+        BigInt::from_limbs(self.mul(x, &one))
+    }
+
+    /// The value `1` in Montgomery form.
+    pub(crate) fn one(&self) -> Vec<Base> {
+        self.encode(&BigInt::one())
+    }
+
+    /// Multiplies two residues in Montgomery form, giving `a * b * R^-1 mod n`. The running
+    /// time depends only on the number of limbs in the modulus.
+    pub(crate) fn mul(&self, a: &[Base], b: &[Base]) -> Vec<Base> {
+        let mut t = self.mul_unreduced(a, b);
+        let len = self.len();
+
+        let mut out = t[..len].to_vec();
+        let borrow = sub_limbs(&mut out, &self.n);
+
+        // the product is below `2n`, so at most one subtraction is needed, and it is kept
+        // unless it borrowed from a product which fit in `len` limbs
+        let keep = Choice::from(borrow) & !Choice::from_nonzero(t[len]);
+        t.truncate(len);
+        select_limbs(&mut out, &t, keep);
+        out
+    }
+
+    // Coarsely integrated operand scanning, which interleaves the multiplication
+    // with the reduction one limb at a time. The result is below `2n` and has
+    // one more limb than the modulus.
+    fn mul_unreduced(&self, a: &[Base], b: &[Base]) -> Vec<Base> {
+        let len = self.len();
+        let mut t = vec![0 as Base; len + 2];
+        let (mut lo, mut hi, mut c, mut overflow);
+
+        for &b in &b[..len] {
+            c = 0;
+            for j in 0..len {
+                (lo, hi) = a[j].carrying_mul(b, c);
+                (t[j], overflow) = lo.overflowing_add(t[j]);
+                c = hi + overflow as Base;
+            }
+            (t[len], overflow) = t[len].overflowing_add(c);
+            t[len + 1] = overflow as Base;
+
+            let m = t[0].wrapping_mul(self.n_inv);
+            (lo, hi) = m.carrying_mul(self.n[0], 0);
+            (_, overflow) = lo.overflowing_add(t[0]);
+            c = hi + overflow as Base;
+
+            for j in 1..len {
+                (lo, hi) = m.carrying_mul(self.n[j], c);
+                (t[j - 1], overflow) = lo.overflowing_add(t[j]);
+                c = hi + overflow as Base;
+            }
+            (t[len - 1], overflow) = t[len].overflowing_add(c);
+            t[len] = t[len + 1] + overflow as Base;
+        }
+
+        t.truncate(len + 1);
+        t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let n = BigInt::from(0xffff_ffff_ffff_ffc5_u64) * BigInt::from(0xffff_fffb_u32);
+        let mont = Montgomery::new(&n);
+
+        for x in [0u64, 1, 2, 0xdead_beef, u64::MAX] {
+            assert_eq!(mont.decode(&mont.encode(&BigInt::from(x))), x);
+        }
+    }
+
+    #[test]
+    fn mul() {
+        let n = (BigInt::one() << 127u32) - 1;
+        let a = BigInt::from(0x0123_4567_89ab_cdef_u64) << 60u32;
+        let b = BigInt::from(0xfedc_ba98_7654_3210_u64) << 50u32;
+        let mont = Montgomery::new(&n);
+
+        assert_eq!(
+            mont.decode(&mont.mul(&mont.encode(&a), &mont.encode(&b))),
+            (&a * &b) % &n
+        );
+    }
+}
@@ -1,38 +1,87 @@
+use std::iter::Product;
 use std::mem;
 use std::ops::{Mul, MulAssign};
 
+use crate::arithmetic::add::add_limbs;
+use crate::arithmetic::sub::sub_limbs;
 use crate::{Base, BigInt};
 
 impl BigInt {
-    // #[cfg(num)]
+    /// Raises `self` to the power of `exp`. Negative exponents truncate towards zero, the same as
+    /// integer division would, and panic if `self` is zero.
     pub fn pow(&self, exp: impl Into<BigInt>) -> Self {
         let exp: BigInt = exp.into();
-        let mut i = BigInt::from_le_bytes(
-            &crate::to_num_bigint(self)
-                .pow((&exp).try_into().unwrap())
-                .to_bytes_le()
-                .1,
-        );
-        i.signed = exp.is_even() || self.signed;
-        i
+
+        if exp.is_negative() {
+            assert!(*self != 0, "attempt to divide by zero");
+            return if self.abs() == 1 {
+                self.pow(exp.abs())
+            } else {
+                BigInt::zero()
+            };
+        }
+
+        let mut val = sliding_window(self.abs(), &exp, BigInt::one(), |a, b| a * b);
+        val.signed = self.signed && exp.is_odd();
+        val
+    }
+}
+
+/// Raises `base` to the power of `exp` using sliding-window exponentiation, where `mul`
+/// multiplies two values and `one` is its identity.
+///
+/// Runs of zero bits only cost a squaring each, and every window of up to `k` bits ending in a
+/// one costs a single multiplication from a table of the odd powers of `base`.
+pub(crate) fn sliding_window<T>(base: T, exp: &BigInt, one: T, mul: impl Fn(&T, &T) -> T) -> T {
+    const BITS: usize = Base::BITS as usize;
+
+    let bits = exp.bits();
+    let k = match bits {
+        0..=7 => 1,
+        8..=23 => 2,
+        24..=79 => 3,
+        80..=239 => 4,
+        240..=671 => 5,
+        _ => 6,
+    };
+    let bit = |i: usize| exp.data[i / BITS] >> (i % BITS) & 1 == 1;
+
+    // base^1, base^3, ..., base^(2^k - 1)
+    let mut table = vec![base];
+    if k > 1 {
+        let square = mul(&table[0], &table[0]);
+        for i in 1..(1 << (k - 1)) {
+            let next = mul(&table[i - 1], &square);
+            table.push(next);
+        }
     }
-    // pub fn pow(&self, exp: &BigInt) -> Self {
-    //     if exp == 0 {
-    //         return BigInt::one();
-    //     } else if exp < 0 {
-    //         return BigInt::zero();
-    //     }
 
-    //     let base = self.clone();
-    //     let mut val = self.clone();
-    //     let mut exp = exp.clone();
+    let mut val = one;
+    let mut i = bits;
+
+    while i > 0 {
+        if !bit(i - 1) {
+            val = mul(&val, &val);
+            i -= 1;
+            continue;
+        }
 
-    //     while exp > 1 {
-    //         val *= base.clone();
-    //         exp -= 1;
-    //     }
-    //     val
-    // }
+        // the window spans bits `j..i` and always starts and ends with a one
+        let mut j = i.saturating_sub(k);
+        while !bit(j) {
+            j += 1;
+        }
+
+        let mut window = 0;
+        for b in (j..i).rev() {
+            val = mul(&val, &val);
+            window = window << 1 | bit(b) as usize;
+        }
+        val = mul(&val, &table[window >> 1]);
+        i = j;
+    }
+
+    val
 }
 
 impl Mul for BigInt {
@@ -46,43 +95,384 @@ impl Mul for BigInt {
 
 impl MulAssign<Self> for BigInt {
     fn mul_assign(&mut self, mut other: Self) {
-        if self.data.len() < other.data.len() {
-            mem::swap(self, &mut other);
-        }
-
-        if self == 0 || other == 0 {
-            *self = BigInt::zero();
-        } else if self.abs() == 1 {
-            self.signed = self.signed ^ other.signed;
-            self.data = other.data;
-        } else if other.abs() != 1 {
-            let mut chunks = vec![0 as Base; self.data.len() + other.data.len()];
-            let (mut prod, mut carry, mut overflow);
-
-            for i in 0..(self.data.len()) {
-                for j in 0..(other.data.len()) {
-                    (prod, carry) = self.data[i].carrying_mul(other.data[j], 0);
-
-                    let mut k = i + j;
-                    (chunks[k], overflow) = chunks[k].overflowing_add(prod);
-                    k += 1;
-                    (chunks[k], overflow) = chunks[k].carrying_add(carry, overflow);
-
-                    while overflow {
-                        k += 1;
-                        (chunks[k], overflow) = chunks[k].carrying_add(0, overflow);
-                    }
-                }
+        // a single limb factor scales the other in place, which avoids allocating for small values
+        if self.data.len() == 1 {
+            mem::swap(&mut self.data, &mut other.data);
+        }
+        if other.data.len() == 1 {
+            let d = other.data[0];
+            let mut carry = 0;
+            for x in self.data.iter_mut() {
+                (*x, carry) = x.carrying_mul(d, carry);
             }
+            if carry != 0 {
+                self.data.push(carry);
+            }
+        } else {
+            self.data = mul_limbs(&self.data, &other.data).into();
+        }
+        self.signed = (self.signed ^ other.signed) && self.data.iter().any(|&e| e != 0);
+        self.trim();
+    }
+}
+
+// Operands with fewer limbs than this are multiplied with the schoolbook method.
+const KARATSUBA_THRESHOLD: usize = 48;
+
+// Operands with at least this many limbs are multiplied with Toom-Cook 3-way.
+const TOOM_3_THRESHOLD: usize = 1024;
+
+// Operands with at least this many limbs are multiplied with number-theoretic transforms.
+const NTT_THRESHOLD: usize = 12288;
+
+/// Multiplies two little-endian limb slices, picking an algorithm based on their sizes. The
+/// product has exactly `a.len() + b.len()` limbs.
+pub(crate) fn mul_limbs(a: &[Base], b: &[Base]) -> Vec<Base> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+
+    if b.len() < KARATSUBA_THRESHOLD {
+        schoolbook(a, b)
+    } else if a.len() >= 2 * b.len() {
+        unbalanced(a, b)
+    } else if b.len() < TOOM_3_THRESHOLD {
+        karatsuba(a, b)
+    } else if b.len() < NTT_THRESHOLD {
+        toom_3(a, b)
+    } else {
+        ntt_mul(a, b)
+    }
+}
+
+// Splits `x` into its lowest `at` limbs and the rest.
+fn split(x: &[Base], at: usize) -> (&[Base], &[Base]) {
+    x.split_at(at.min(x.len()))
+}
+
+// Long multiplication, O(n * m).
+fn schoolbook(a: &[Base], b: &[Base]) -> Vec<Base> {
+    let mut prod = vec![0 as Base; a.len() + b.len()];
+    let (mut lo, mut hi, mut carry, mut overflow);
+
+    for (i, &x) in a.iter().enumerate() {
+        carry = 0;
+        for (j, &y) in b.iter().enumerate() {
+            (lo, hi) = x.carrying_mul(y, carry);
+            (prod[i + j], overflow) = prod[i + j].overflowing_add(lo);
+            carry = hi + overflow as Base;
+        }
+        prod[i + b.len()] = carry;
+    }
+
+    prod
+}
 
-            while chunks.ends_with(&[0]) && chunks.len() > 1 {
-                chunks.pop();
+// Multiplies a long operand by a much shorter one, one short-operand-sized chunk at a time, so
+// that each partial product is balanced.
+fn unbalanced(a: &[Base], b: &[Base]) -> Vec<Base> {
+    let mut prod = vec![0 as Base; a.len() + b.len()];
+
+    for (i, chunk) in a.chunks(b.len()).enumerate() {
+        add_limbs(&mut prod, &mul_limbs(chunk, b), i * b.len());
+    }
+
+    prod.truncate(a.len() + b.len());
+    prod
+}
+
+// Splits each operand into halves `x = x1 * B^m + x0` and recombines three half-sized products,
+// O(n^1.585).
+//
+// `a * b = z2 * B^2m + ((a0 + a1)(b0 + b1) - z2 - z0) * B^m + z0`
+fn karatsuba(a: &[Base], b: &[Base]) -> Vec<Base> {
+    let m = (a.len().max(b.len()) + 1) / 2;
+    let (a0, a1) = split(a, m);
+    let (b0, b1) = split(b, m);
+
+    let z0 = mul_limbs(a0, b0);
+    let z2 = mul_limbs(a1, b1);
+
+    let mut a01 = a0.to_vec();
+    add_limbs(&mut a01, a1, 0);
+    let mut b01 = b0.to_vec();
+    add_limbs(&mut b01, b1, 0);
+
+    let mut z1 = mul_limbs(&a01, &b01);
+    sub_limbs(&mut z1, &z0);
+    sub_limbs(&mut z1, &z2);
+
+    let mut prod = z0;
+    add_limbs(&mut prod, &z1, m);
+    add_limbs(&mut prod, &z2, 2 * m);
+
+    prod.resize(a.len() + b.len(), 0);
+    prod
+}
+
+// Splits each operand into thirds, evaluates both as polynomials at `0, 1, -1, -2, inf`, and
+// interpolates the five pointwise products back into the product polynomial, O(n^1.465).
+//
+// The evaluation and interpolation sequences are from Bodrato, "Towards Optimal Toom-Cook
+// Multiplication for Univariate and Multivariate Polynomials in Characteristic 2 and 0".
+fn toom_3(a: &[Base], b: &[Base]) -> Vec<Base> {
+    let k = (a.len().max(b.len()) + 2) / 3;
+
+    let thirds = |x: &[Base]| {
+        let (x0, x) = split(x, k);
+        let (x1, x2) = split(x, k);
+        [x0, x1, x2].map(|e| BigInt::from_limbs(e.to_vec()))
+    };
+    let [a0, a1, a2] = thirds(a);
+    let [b0, b1, b2] = thirds(b);
+
+    let evaluate = |x0: BigInt, x1: BigInt, x2: BigInt| {
+        let p = &x0 + &x2;
+        let p1 = &p + &x1;
+        let p_1 = p - &x1;
+        let p_2 = ((&p_1 + &x2) << 1u8) - &x0;
+        [x0, p1, p_1, p_2, x2]
+    };
+    let [a0, a1, a_1, a_2, a_inf] = evaluate(a0, a1, a2);
+    let [b0, b1, b_1, b_2, b_inf] = evaluate(b0, b1, b2);
+
+    let r0 = a0 * b0;
+    let r1 = a1 * b1;
+    let r_1 = a_1 * b_1;
+    let r_2 = a_2 * b_2;
+    let r_inf = a_inf * b_inf;
+
+    let mut c3 = r_2 - &r1;
+    exact_div(&mut c3, 3);
+    let mut c1 = r1 - &r_1;
+    exact_div(&mut c1, 2);
+    let mut c2 = r_1 - &r0;
+    c3 = c2.clone() - c3;
+    exact_div(&mut c3, 2);
+    c3 += r_inf.clone() << 1u8;
+    c2 += &c1 - &r_inf;
+    c1 -= &c3;
+
+    let mut prod = Vec::from(r0.data);
+    for (i, c) in [c1, c2, c3, r_inf].into_iter().enumerate() {
+        add_limbs(&mut prod, &c.data, (i + 1) * k);
+    }
+
+    prod.resize(a.len() + b.len(), 0);
+    prod
+}
+
+// Arithmetic modulo a prime `p < 2^62`, with values kept in Montgomery form `x * 2^64 mod p`.
+struct NttPrime {
+    p: u64,
+    // `-p^-1 mod 2^64`
+    neg_inv: u64,
+    // `2^128 mod p`, to convert into Montgomery form
+    r2: u64,
+    // a generator of the multiplicative group
+    root: u64,
+}
+
+impl NttPrime {
+    const fn new(p: u64, root: u64) -> Self {
+        // Newton's iteration doubles the correct low bits of the inverse each step
+        let mut inv = 1u64;
+        let mut i = 0;
+        while i < 6 {
+            inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+            i += 1;
+        }
+
+        let r = (1u128 << 64) % p as u128;
+        Self {
+            p,
+            neg_inv: inv.wrapping_neg(),
+            r2: (r * r % p as u128) as u64,
+            root,
+        }
+    }
+
+    // Montgomery reduction of `x * 2^-64`, for `x < p * 2^64`.
+    fn reduce(&self, x: u128) -> u64 {
+        let m = (x as u64).wrapping_mul(self.neg_inv);
+        let t = ((x + m as u128 * self.p as u128) >> 64) as u64;
+        if t >= self.p {
+            t - self.p
+        } else {
+            t
+        }
+    }
+
+    fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    fn add(&self, a: u64, b: u64) -> u64 {
+        let t = a + b;
+        if t >= self.p {
+            t - self.p
+        } else {
+            t
+        }
+    }
+
+    fn sub(&self, a: u64, b: u64) -> u64 {
+        if a >= b {
+            a - b
+        } else {
+            a + self.p - b
+        }
+    }
+
+    fn encode(&self, x: u64) -> u64 {
+        self.mul(x, self.r2)
+    }
+
+    fn decode(&self, x: u64) -> u64 {
+        self.reduce(x as u128)
+    }
+
+    fn pow(&self, mut base: u64, mut exp: u64) -> u64 {
+        let mut acc = self.encode(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = self.mul(acc, base);
             }
+            base = self.mul(base, base);
+            exp >>= 1;
+        }
+        acc
+    }
+
+    // An in-place iterative radix-2 transform of a power of two length, in Montgomery form.
+    fn transform(&self, x: &mut [u64], inverse: bool) {
+        let n = x.len();
 
-            self.signed = self.signed ^ other.signed;
-            self.data = chunks;
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+            if i < j {
+                x.swap(i, j);
+            }
         }
+
+        let mut len = 2;
+        while len <= n {
+            let mut w = self.pow(self.encode(self.root), (self.p - 1) / len as u64);
+            if inverse {
+                w = self.pow(w, self.p - 2);
+            }
+
+            let mut twiddles = vec![self.encode(1); len / 2];
+            for i in 1..len / 2 {
+                twiddles[i] = self.mul(twiddles[i - 1], w);
+            }
+
+            for chunk in x.chunks_exact_mut(len) {
+                let (lo, hi) = chunk.split_at_mut(len / 2);
+                for ((u, v), &w) in lo.iter_mut().zip(hi).zip(&twiddles) {
+                    let t = self.mul(*v, w);
+                    (*u, *v) = (self.add(*u, t), self.sub(*u, t));
+                }
+            }
+            len <<= 1;
+        }
+
+        if inverse {
+            let n_inv = self.pow(self.encode(n as u64), self.p - 2);
+            for e in x.iter_mut() {
+                *e = self.mul(*e, n_inv);
+            }
+        }
+    }
+
+    // The cyclic convolution of `a` and `b` modulo `p`, with `n` a large enough power of two.
+    fn convolve(&self, a: &[Base], b: &[Base], n: usize) -> Vec<u64> {
+        let load = |x: &[Base]| {
+            let mut v = x.iter().map(|&e| self.encode(e as u64)).collect::<Vec<_>>();
+            v.resize(n, 0);
+            self.transform(&mut v, false);
+            v
+        };
+
+        let (fa, fb) = (load(a), load(b));
+        let mut prod = fa
+            .iter()
+            .zip(&fb)
+            .map(|(&x, &y)| self.mul(x, y))
+            .collect::<Vec<_>>();
+        self.transform(&mut prod, true);
+        prod.into_iter().map(|e| self.decode(e)).collect()
+    }
+}
+
+// Primes of the form `k * 2^50 + 1` with their smallest generators, so transforms of up to `2^50`
+// points exist. Their product is above `2^185`, enough for each coefficient of the product of two
+// operands with up to `2^57` limbs.
+const NTT_PRIMES: [NttPrime; 3] = [
+    NttPrime::new(0x3fdc_0000_0000_0001, 3),
+    NttPrime::new(0x3f18_0000_0000_0001, 10),
+    NttPrime::new(0x3ec4_0000_0000_0001, 37),
+];
+
+// Treats the limbs as polynomial coefficients, convolves them modulo three primes, and recovers
+// each coefficient of the product with the Chinese remainder theorem before propagating carries,
+// O(n log n).
+fn ntt_mul(a: &[Base], b: &[Base]) -> Vec<Base> {
+    let [p1, p2, p3] = &NTT_PRIMES;
+    let n = (a.len() + b.len()).next_power_of_two();
+    let (c1, c2, c3) = (
+        p1.convolve(a, b, n),
+        p2.convolve(a, b, n),
+        p3.convolve(a, b, n),
+    );
+
+    // Garner's algorithm, with the inverses in Montgomery form so multiplying by them leaves the
+    // other operand out of it
+    let p1_p2 = p1.p as u128 * p2.p as u128;
+    let inv_p1 = p2.pow(p2.encode(p1.p % p2.p), p2.p - 2);
+    let inv_p1_p2 = p3.pow(p3.encode((p1_p2 % p3.p as u128) as u64), p3.p - 2);
+    let p1_mod_p3 = p3.encode(p1.p % p3.p);
+
+    // a 256-bit accumulator, holding the coefficient plus the carry from the ones below it
+    let mut acc = (0u128, 0u128);
+    let add = |acc: &mut (u128, u128), lo: u128, hi: u128| {
+        let (sum, carry) = acc.0.overflowing_add(lo);
+        *acc = (sum, acc.1 + hi + carry as u128);
+    };
+
+    let mut prod = Vec::with_capacity(a.len() + b.len());
+    for i in 0..a.len() + b.len() {
+        let r1 = c1[i];
+        let v2 = p2.mul(p2.sub(c2[i], r1 % p2.p), inv_p1);
+        let v3 = p3.mul(
+            p3.sub(p3.sub(c3[i], r1 % p3.p), p3.mul(v2, p1_mod_p3)),
+            inv_p1_p2,
+        );
+
+        // r1 + v2 * p1 + v3 * p1 * p2
+        add(&mut acc, r1 as u128 + v2 as u128 * p1.p as u128, 0);
+        add(&mut acc, v3 as u128 * (p1_p2 as u64) as u128, 0);
+        let mid = v3 as u128 * (p1_p2 >> 64);
+        add(&mut acc, mid << 64, mid >> 64);
+
+        prod.push(acc.0 as Base);
+        acc = (
+            acc.0 >> Base::BITS | acc.1 << (u128::BITS - Base::BITS),
+            acc.1 >> Base::BITS,
+        );
     }
+
+    prod
+}
+
+// Divides `x` by `d`, which must divide it evenly.
+fn exact_div(x: &mut BigInt, d: Base) {
+    let rem = x.div_rem_limb(d);
+    debug_assert_eq!(rem, 0);
 }
 
 impl Mul<&Self> for BigInt {
@@ -138,6 +528,18 @@ macro_rules! impl_primitive_mul {
 
 impl_primitive_mul!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
 
+impl Product for BigInt {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(BigInt::one(), |acc, x| acc * x)
+    }
+}
+
+impl<'a> Product<&'a BigInt> for BigInt {
+    fn product<I: Iterator<Item = &'a BigInt>>(iter: I) -> Self {
+        iter.fold(BigInt::one(), |acc, x| acc * x)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,7 +581,147 @@ mod tests {
         BigInt::from(BaseExt::MAX),
         BigInt {
             signed: false,
-            data: vec![1, 0, Base::MAX - 1, Base::MAX]
+            data: [1, 0, Base::MAX - 1, Base::MAX].into()
         }
     );
+
+    mod algorithms {
+        use super::*;
+
+        // xorshift, so the operands are reproducible without a dependency on an rng
+        fn limbs(len: usize, seed: &mut u64) -> Vec<Base> {
+            (0..len)
+                .map(|_| {
+                    *seed ^= *seed << 13;
+                    *seed ^= *seed >> 7;
+                    *seed ^= *seed << 17;
+                    *seed as Base
+                })
+                .collect()
+        }
+
+        macro_rules! test_algorithms {
+            ($name:ident: $a:expr, $b:expr) => {
+                #[test]
+                fn $name() {
+                    let mut seed = 0x2545_f491_4f6c_dd1d;
+                    let a = limbs($a, &mut seed);
+                    let b = limbs($b, &mut seed);
+                    let expected = schoolbook(&a, &b);
+
+                    assert_eq!(karatsuba(&a, &b), expected, "karatsuba");
+                    assert_eq!(toom_3(&a, &b), expected, "toom-3");
+                    assert_eq!(ntt_mul(&a, &b), expected, "ntt");
+                    assert_eq!(mul_limbs(&a, &b), expected, "dispatch");
+                    assert_eq!(mul_limbs(&b, &a), expected, "dispatch swapped");
+                }
+            };
+        }
+
+        test_algorithms!(single: 1, 1);
+
+        test_algorithms!(small: 5, 3);
+
+        test_algorithms!(karatsuba_threshold: KARATSUBA_THRESHOLD, KARATSUBA_THRESHOLD);
+
+        test_algorithms!(odd_lengths: 77, 61);
+
+        test_algorithms!(unbalanced: 300, 40);
+
+        test_algorithms!(toom_3_threshold: TOOM_3_THRESHOLD, TOOM_3_THRESHOLD + 1);
+
+        test_algorithms!(large: 1100, 1050);
+
+        #[test]
+        fn ntt_threshold() {
+            let mut seed = 0x2545_f491_4f6c_dd1d;
+            let a = limbs(NTT_THRESHOLD, &mut seed);
+            let b = limbs(NTT_THRESHOLD + 3, &mut seed);
+
+            assert_eq!(ntt_mul(&a, &b), toom_3(&a, &b));
+            assert_eq!(mul_limbs(&a, &b), toom_3(&a, &b));
+        }
+
+        #[test]
+        fn ntt_squares() {
+            // the largest possible coefficients, where any error in recombination would show
+            let a = vec![Base::MAX; 3000];
+            let x = BigInt::from_limbs(a.clone());
+            let expected = (BigInt::one() << (6000 * Base::BITS as usize))
+                - (BigInt::one() << (3000 * Base::BITS as usize + 1))
+                + 1u8;
+
+            assert_eq!(BigInt::from_limbs(ntt_mul(&a, &a)), expected);
+            assert_eq!(&x * &x, expected);
+        }
+
+        #[test]
+        fn all_ones() {
+            let a = vec![Base::MAX; 400];
+            let b = vec![Base::MAX; 350];
+            let expected = schoolbook(&a, &b);
+
+            assert_eq!(karatsuba(&a, &b), expected, "karatsuba");
+            assert_eq!(toom_3(&a, &b), expected, "toom-3");
+            assert_eq!(ntt_mul(&a, &b), expected, "ntt");
+        }
+
+        #[test]
+        fn signs() {
+            let a = BigInt::from_limbs(vec![Base::MAX; 250]);
+            let b = BigInt::from_limbs(vec![Base::MAX / 3; 250]);
+
+            let neg = |x: &BigInt| BigInt::zero() - x;
+
+            assert_eq!(neg(&a) * neg(&b), &a * &b);
+            assert_eq!(neg(&a) * &b, neg(&(&a * &b)));
+        }
+    }
+
+    mod pow {
+        use super::*;
+
+        macro_rules! test_pow {
+            ($name:ident: $a:expr, $b:expr, $e:expr) => {
+                #[test]
+                fn $name() {
+                    assert_eq!($a.pow($b), $e);
+                }
+            };
+        }
+
+        test_pow!(zero_zero: BigInt::zero(), 0, 1);
+
+        test_pow!(two_ten: BigInt::from(2), 10, 1024);
+
+        test_pow!(neg_odd: BigInt::from(-3), 3, -27);
+
+        test_pow!(neg_even: BigInt::from(-3), 4, 81);
+
+        test_pow!(neg_exp: BigInt::from(2), -1, 0);
+
+        test_pow!(neg_one_neg_exp: BigInt::from(-1), -3, -1);
+
+        test_pow!(big: BigInt::from(2), 300, BigInt::one() << 300);
+
+        test_pow!(
+            bigger: BigInt::from(Base::MAX),
+            5,
+            BigInt::from(Base::MAX) * Base::MAX * Base::MAX * Base::MAX * Base::MAX
+        );
+
+        #[test]
+        #[should_panic = "attempt to divide by zero"]
+        fn zero_neg_exp() {
+            BigInt::zero().pow(-1);
+        }
+    }
+
+    #[test]
+    fn product() {
+        let xs = (1..=30).map(BigInt::from).collect::<Vec<_>>();
+        assert_eq!(xs.iter().product::<BigInt>(), BigInt::factorial(30));
+        assert_eq!(xs.into_iter().product::<BigInt>(), BigInt::factorial(30));
+        assert_eq!(std::iter::empty::<BigInt>().product::<BigInt>(), 1);
+    }
 }
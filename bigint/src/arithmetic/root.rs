@@ -0,0 +1,203 @@
+use crate::BigInt;
+
+impl BigInt {
+    /// Calculates the floor of the square root of `self`. Panics if `self` is negative.
+    pub fn sqrt(&self) -> Self {
+        assert!(!self.is_negative(), "attempt to take the square root of a negative number");
+        self.nth_root(2)
+    }
+
+    /// Calculates the floor of the `n`th root of `self`, so odd roots of negative values round
+    /// away from zero, and `(-9).nth_root(3)` is `-3`. Panics if `n` is zero, or if `n` is even
+    /// and `self` is negative.
+    pub fn nth_root(&self, n: u32) -> Self {
+        assert!(n != 0, "attempt to take the zeroth root");
+        assert!(
+            n % 2 == 1 || !self.is_negative(),
+            "attempt to take an even root of a negative number"
+        );
+
+        if n == 1 || self.abs() <= 1 {
+            return self.clone();
+        }
+
+        let a = self.abs();
+        let n_1 = BigInt::from(n - 1);
+
+        // newton's method, starting from a power of two above the root, decreases monotonically
+        // until it reaches the floor of the root
+        let mut x = BigInt::one() << ((a.bits() + n as usize - 1) / n as usize);
+        loop {
+            let y = (&n_1 * &x + &a / x.pow(n - 1)) / n;
+            if y >= x {
+                break;
+            }
+            x = y;
+        }
+
+        // the floor of a negative root is one further from zero unless the root is exact
+        if self.is_negative() {
+            if x.pow(n) != a {
+                x += 1u8;
+            }
+            x.signed = true;
+        }
+        x
+    }
+
+    /// Returns true if `self` is the square of an integer. Negative values never are.
+    pub fn is_perfect_square(&self) -> bool {
+        // squares are always 0, 1, 4 or 9 modulo 16
+        if self.is_negative() || ![0, 1, 4, 9].contains(&(self.data[0] & 0xf)) {
+            return false;
+        }
+
+        let root = self.sqrt();
+        &root * &root == *self
+    }
+
+    /// Returns true if `self` is `b^k` for some integer `b` and some `k >= 2`. Values with a
+    /// magnitude below `2` are not considered to be perfect powers.
+    pub fn is_perfect_power(&self) -> bool {
+        self.perfect_power().is_some()
+    }
+
+    /// Finds `(b, k)` with `b^k == self` and `k >= 2` as large as possible, or `None` if `self`
+    /// is not a perfect power. Values with a magnitude below `2` are not considered to be perfect
+    /// powers.
+    pub fn perfect_power(&self) -> Option<(BigInt, u32)> {
+        if self.abs() < 2 {
+            return None;
+        }
+
+        // any power is a power of a prime exponent, and the base is at least 2, so `k` is at most
+        // the number of bits
+        let bits = self.bits() as u32;
+        let start = if self.is_negative() { 3 } else { 2 };
+
+        (start..=bits)
+            .filter(|k| (2..*k).take_while(|d| d * d <= *k).all(|d| k % d != 0))
+            .find_map(|k| {
+                let root = self.nth_root(k);
+                if root.pow(k) == *self {
+                    Some(match root.perfect_power() {
+                        Some((b, e)) => (b, e * k),
+                        None => (root, k),
+                    })
+                } else {
+                    None
+                }
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Base, BaseExt};
+
+    macro_rules! test_root {
+        ($name:ident: $a:expr, $n:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!(BigInt::from($a).nth_root($n), $e);
+            }
+        };
+    }
+
+    test_root!(zero: 0, 2, 0);
+
+    test_root!(one: 1, 5, 1);
+
+    test_root!(first: 12345, 1, 12345);
+
+    test_root!(square: 144, 2, 12);
+
+    test_root!(below_square: 143, 2, 11);
+
+    test_root!(cube: 1_000_000_000_000_u64, 3, 10_000);
+
+    test_root!(below_cube: 999_999_999_999_u64, 3, 9_999);
+
+    test_root!(neg_cube: -27, 3, -3);
+
+    test_root!(neg_below_cube: -26, 3, -3);
+
+    test_root!(neg_above_cube: -28, 3, -4);
+
+    test_root!(neg_fifth: -33, 5, -3);
+
+    test_root!(max: BaseExt::MAX, 2, Base::MAX);
+
+    test_root!(high_root: BaseExt::MAX, 127, 2);
+
+    #[test]
+    fn sqrt() {
+        for i in 0..2000u32 {
+            let root = BigInt::from(i).sqrt();
+            assert!(&root * &root <= i && (&root + BigInt::one()) * (&root + BigInt::one()) > i, "{i}");
+        }
+
+        let x = BigInt::from(0x1234_5678_9abc_def0_u64) << 500u16;
+        let root = x.sqrt();
+        assert!(&root * &root <= x && (&root + BigInt::one()) * (&root + BigInt::one()) > x);
+    }
+
+    #[test]
+    #[should_panic = "attempt to take the square root of a negative number"]
+    fn sqrt_negative() {
+        BigInt::from(-4).sqrt();
+    }
+
+    #[test]
+    #[should_panic = "attempt to take an even root of a negative number"]
+    fn even_root_negative() {
+        BigInt::from(-16).nth_root(4);
+    }
+
+    #[test]
+    fn is_perfect_square() {
+        for i in 0..10_000u32 {
+            assert_eq!(BigInt::from(i).is_perfect_square(), (0..100u32).any(|e| e * e == i), "{i}");
+        }
+
+        let x = BigInt::from(u128::MAX - 158).pow(2);
+        assert!(x.is_perfect_square());
+        assert!(!(x + 1u8).is_perfect_square());
+        assert!(!BigInt::from(-4).is_perfect_square());
+    }
+
+    mod perfect_power {
+        use super::*;
+
+        macro_rules! test_perfect_power {
+            ($name:ident: $a:expr, $e:expr) => {
+                #[test]
+                fn $name() {
+                    let a: BigInt = $a;
+                    let e: Option<(BigInt, u32)> = $e;
+                    assert_eq!(a.perfect_power(), e);
+                    assert_eq!(a.is_perfect_power(), e.is_some());
+                }
+            };
+        }
+
+        test_perfect_power!(zero: BigInt::zero(), None);
+
+        test_perfect_power!(prime: BigInt::from(97), None);
+
+        test_perfect_power!(square: BigInt::from(49), Some((BigInt::from(7), 2)));
+
+        test_perfect_power!(composite_exponent: BigInt::from(64), Some((BigInt::from(2), 6)));
+
+        test_perfect_power!(mixed: BigInt::from(72), None);
+
+        test_perfect_power!(neg_cube: BigInt::from(-125), Some((BigInt::from(-5), 3)));
+
+        test_perfect_power!(neg_square: BigInt::from(-49), None);
+
+        test_perfect_power!(neg_composite_exponent: BigInt::from(-64), Some((BigInt::from(-4), 3)));
+
+        test_perfect_power!(big: BigInt::from(1_000_003).pow(12), Some((BigInt::from(1_000_003), 12)));
+    }
+}
@@ -1,7 +1,8 @@
 use std::mem;
 use std::ops::{Sub, SubAssign};
 
-use crate::BigInt;
+use crate::cmp::cmp_limbs;
+use crate::{Base, BigInt};
 
 impl Sub<Self> for BigInt {
     type Output = Self;
@@ -18,23 +19,21 @@ impl SubAssign<Self> for BigInt {
             other.signed = self.signed;
             *self += other;
         } else {
-            if self.abs() < other.abs() {
+            if cmp_limbs(&self.data, &other.data).is_lt() {
                 mem::swap(self, &mut other);
                 self.signed ^= true;
             }
 
-            let mut overflow;
+            let mut borrow = false;
+            let (a, b) = (&mut *self.data, &*other.data);
             let mut i = 0;
 
-            while i < other.data.len() {
-                (self.data[i], overflow) = self.data[i].overflowing_sub(other.data[i]);
-
-                let mut j = i + 1;
-                while overflow && j < self.data.len() {
-                    (self.data[j], overflow) = self.data[j].overflowing_sub(1);
-                    j += 1;
-                }
-
+            while i < b.len() {
+                (a[i], borrow) = a[i].borrowing_sub(b[i], borrow);
+                i += 1;
+            }
+            while borrow && i < a.len() {
+                (a[i], borrow) = a[i].borrowing_sub(0, borrow);
                 i += 1;
             }
         }
@@ -45,6 +44,15 @@ impl SubAssign<Self> for BigInt {
     }
 }
 
+// Subtracts `b` from `a` in place, returning whether the result borrowed.
+pub(crate) fn sub_limbs(a: &mut [Base], b: &[Base]) -> bool {
+    let mut borrow = false;
+    for (i, x) in a.iter_mut().enumerate() {
+        (*x, borrow) = x.borrowing_sub(*b.get(i).unwrap_or(&0), borrow);
+    }
+    borrow
+}
+
 impl Sub<&Self> for BigInt {
     type Output = Self;
 
@@ -101,7 +109,7 @@ impl_primitive_sub!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isi
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Base, BaseExt};
+    use crate::BaseExt;
 
     macro_rules! test_sub {
         ($name:ident: $a:expr, $b:expr, $e:expr) => {
@@ -123,7 +131,7 @@ mod tests {
     test_sub!(carry: BigInt::from(Base::MAX as BaseExt + 1), BigInt::one(), Base::MAX);
 
     test_sub!(big:
-        BigInt { signed: false, data: vec![Base::MAX - 1, Base::MAX, 1] },
+        BigInt { signed: false, data: [Base::MAX - 1, Base::MAX, 1].into() },
         BigInt::from(BaseExt::MAX),
         BaseExt::MAX
     );
@@ -145,14 +153,14 @@ mod tests {
             big_inv: BigInt::from(Base::MAX),
             BigInt {
                 signed: true,
-                data: vec![Base::MAX]
+                data: [Base::MAX].into()
             },
             Base::MAX as BaseExt * 2
         );
 
         test_sub!(big_neg_big:
             BigInt::from(Base::MAX - 1),
-            BigInt { signed: true, data: vec![(Base::MAX - 1) / 2] },
+            BigInt { signed: true, data: [(Base::MAX - 1) / 2].into() },
             Base::MAX as BaseExt - 1 + ((Base::MAX - 1) / 2) as BaseExt
         );
     }
@@ -39,7 +39,7 @@ print(format($a * $b, 'x'))
 print(format($a // $b, 'x'))
 #print(format($a - ($a // $b), 'x'))
 print(format($a % $b, 'x'))
-#print(format(pow($a, $c, $b)))
+print(format(pow($a, $c, $b), 'x'))
 EOF
 )
 
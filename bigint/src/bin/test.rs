@@ -17,5 +17,5 @@ fn main() {
     println!("{:x}", &a / &b);
     // println!("{:x}", &a % &b);
     println!("{:x}", &a.modulo(&b));
-    // println!("{:x}", &a.mod_pow(&c, &b));
+    println!("{:x}", &a.modpow(&c, &b));
 }
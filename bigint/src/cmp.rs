@@ -1,39 +1,29 @@
 use std::cmp::Ordering;
 
-use crate::BigInt;
+use crate::{Base, BigInt};
 
 impl PartialEq for BigInt {
     fn eq(&self, other: &Self) -> bool {
-        self.data == [0] && other.data == [0]
+        *self.data == [0] && *other.data == [0]
             || self.signed == other.signed && self.data == other.data
     }
 }
 
+/// Compares two little-endian limb slices without leading zeros by magnitude.
+pub(crate) fn cmp_limbs(a: &[Base], b: &[Base]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
 impl Ord for BigInt {
     fn cmp(&self, other: &Self) -> Ordering {
         if self == other {
             Ordering::Equal
         } else if self.signed ^ other.signed {
             self.signed.cmp(&other.signed).reverse()
+        } else if !self.signed {
+            cmp_limbs(&self.data, &other.data)
         } else {
-            let a = &self.data;
-            let b = &other.data;
-
-            let ord = a.len().cmp(&b.len()).then_with(|| {
-                for i in (0..(a.len())).rev() {
-                    let ord = a[i].cmp(&b[i]);
-                    if ord.is_ne() {
-                        return ord;
-                    }
-                }
-                unreachable!()
-            });
-
-            if !self.signed {
-                ord
-            } else {
-                ord.reverse()
-            }
+            cmp_limbs(&self.data, &other.data).reverse()
         }
     }
 }
@@ -91,7 +81,6 @@ impl_primitive_cmp!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Base;
 
     mod eq {
         use super::*;
@@ -130,7 +119,7 @@ mod tests {
             zero_eq_neg_zero: BigInt::zero(),
             BigInt {
                 signed: true,
-                data: vec![0]
+                data: [0].into()
             }
         );
 
@@ -161,13 +150,13 @@ mod tests {
         test_ord!(neg_lt_pos: BigInt::from(-2), BigInt::one());
 
         test_ord!(diff_data_size:
-            BigInt { signed: false, data: vec![Base::MAX] },
-            BigInt { signed: false, data: vec![1, Base::MAX] }
+            BigInt { signed: false, data: [Base::MAX].into() },
+            BigInt { signed: false, data: [1, Base::MAX].into() }
         );
 
         test_ord!(diff_data_size_neg:
-            BigInt { signed: true, data: vec![1, Base::MAX] },
-            BigInt { signed: true, data: vec![Base::MAX] }
+            BigInt { signed: true, data: [1, Base::MAX].into() },
+            BigInt { signed: true, data: [Base::MAX].into() }
         );
 
         test_ord!(big_ord: BigInt::from(0x1234567890abcdef_u128), BigInt::from(0xfedcba9876543210_u128));
@@ -0,0 +1,455 @@
+//! Operations whose running time does not depend on the values of secret operands.
+//!
+//! The rest of the crate branches on values, exits early on small operands, and trims leading
+//! zero limbs, so its timing reveals information about the numbers involved. The types here make
+//! choices by masking instead of branching, and `Residue`s always have as many limbs as their
+//! `Modulus`, so the time taken depends only on the sizes of the values and on the modulus, which
+//! is treated as public.
+
+use std::ops::{BitAnd, BitOr, Not};
+
+use crate::arithmetic::montgomery::Montgomery;
+use crate::arithmetic::sub::sub_limbs;
+use crate::uint::Uint;
+use crate::{Base, BigInt};
+
+/// A boolean stored as a mask of all zeros or all ones, so that it can be used without branching.
+#[derive(Clone, Copy, Debug)]
+pub struct Choice(Base);
+
+impl Choice {
+    /// The value `true`.
+    pub const TRUE: Self = Self(Base::MAX);
+
+    /// The value `false`.
+    pub const FALSE: Self = Self(0);
+
+    // Converts a bit which is either `0` or `1`.
+    fn from_bit(bit: Base) -> Self {
+        Self(bit.wrapping_neg())
+    }
+
+    // Whether `x` is not zero.
+    pub(crate) fn from_nonzero(x: Base) -> Self {
+        Self::from_bit((x | x.wrapping_neg()) >> (Base::BITS - 1))
+    }
+}
+
+impl From<bool> for Choice {
+    fn from(b: bool) -> Self {
+        Self::from_bit(b as Base)
+    }
+}
+
+/// Converting back into a `bool` is where the choice stops being secret, so it should only be
+/// done once the result is safe to reveal.
+impl From<Choice> for bool {
+    fn from(c: Choice) -> Self {
+        c.0 != 0
+    }
+}
+
+impl BitAnd for Choice {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        Self(self.0 & other.0)
+    }
+}
+
+impl BitOr for Choice {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        Self(self.0 | other.0)
+    }
+}
+
+impl Not for Choice {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Self(!self.0)
+    }
+}
+
+/// Equality comparisons which take the same time whether or not the values are equal.
+pub trait ConstantTimeEq {
+    /// Returns whether `self` and `other` are equal.
+    fn ct_eq(&self, other: &Self) -> Choice;
+}
+
+/// Selection between two values without branching on which one is chosen.
+pub trait ConditionallySelectable: Sized {
+    /// Returns `b` if `choice` is true, or `a` otherwise.
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self;
+
+    /// Replaces `self` with `other` if `choice` is true.
+    fn conditional_assign(&mut self, other: &Self, choice: Choice) {
+        *self = Self::conditional_select(self, other, choice);
+    }
+
+    /// Swaps `a` and `b` if `choice` is true.
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        let t = Self::conditional_select(a, b, choice);
+        *b = Self::conditional_select(b, a, choice);
+        *a = t;
+    }
+}
+
+macro_rules! impl_primitive_ct {
+    ($($t:ty),*) => {
+        $(
+            impl ConstantTimeEq for $t {
+                fn ct_eq(&self, other: &Self) -> Choice {
+                    let x = self ^ other;
+                    !Choice::from_bit(((x | x.wrapping_neg()) >> (<$t>::BITS - 1)) as Base)
+                }
+            }
+
+            impl ConditionallySelectable for $t {
+                fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+                    let mask = ((choice.0 & 1) as $t).wrapping_neg();
+                    a ^ ((a ^ b) & mask)
+                }
+            }
+        )*
+    }
+}
+
+impl_primitive_ct!(u8, u16, u32, u64, u128, usize);
+
+// Replaces `a` with `b` if `choice` is true. Both must have the same number of limbs.
+pub(crate) fn select_limbs(a: &mut [Base], b: &[Base], choice: Choice) {
+    for (x, &y) in a.iter_mut().zip(b) {
+        *x ^= (*x ^ y) & choice.0;
+    }
+}
+
+// Swaps `a` and `b` if `choice` is true. Both must have the same number of limbs.
+fn swap_limbs(a: &mut [Base], b: &mut [Base], choice: Choice) {
+    for (x, y) in a.iter_mut().zip(b.iter_mut()) {
+        let t = (*x ^ *y) & choice.0;
+        *x ^= t;
+        *y ^= t;
+    }
+}
+
+// Whether `a` and `b` are equal. Both must have the same number of limbs.
+fn eq_limbs(a: &[Base], b: &[Base]) -> Choice {
+    !Choice::from_nonzero(a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)))
+}
+
+// Adds `b` to `a` in place without growing it, returning whether the result carried.
+fn add_limbs(a: &mut [Base], b: &[Base]) -> bool {
+    let mut carry = false;
+    for (x, &y) in a.iter_mut().zip(b) {
+        (*x, carry) = x.carrying_add(y, carry);
+    }
+    carry
+}
+
+impl<const LIMBS: usize> ConstantTimeEq for Uint<LIMBS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        eq_limbs(&self.data, &other.data)
+    }
+}
+
+impl<const LIMBS: usize> ConditionallySelectable for Uint<LIMBS> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mut out = *a;
+        select_limbs(&mut out.data, &b.data, choice);
+        out
+    }
+
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        swap_limbs(&mut a.data, &mut b.data, choice);
+    }
+}
+
+/// An odd modulus for constant-time arithmetic on `Residue`s.
+pub struct Modulus {
+    mont: Montgomery,
+}
+
+/// A value modulo some `Modulus`, kept in Montgomery form with as many limbs as the modulus.
+/// Residues should only be used with the modulus which created them.
+#[derive(Clone, Debug)]
+pub struct Residue {
+    data: Vec<Base>,
+}
+
+impl ConstantTimeEq for Residue {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        eq_limbs(&self.data, &other.data)
+    }
+}
+
+impl ConditionallySelectable for Residue {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mut out = a.clone();
+        select_limbs(&mut out.data, &b.data, choice);
+        out
+    }
+
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        swap_limbs(&mut a.data, &mut b.data, choice);
+    }
+}
+
+impl Modulus {
+    // The number of bits of the exponent handled by each multiplication in `pow`.
+    const WINDOW: usize = 4;
+
+    /// Prepares an odd modulus. Panics if `n` is even.
+    pub fn new(n: &BigInt) -> Self {
+        assert!(
+            n.is_odd(),
+            "constant-time arithmetic requires an odd modulus"
+        );
+        Self {
+            mont: Montgomery::new(n),
+        }
+    }
+
+    /// The value of the modulus.
+    pub fn value(&self) -> BigInt {
+        BigInt::from_limbs(self.mont.modulus().to_vec())
+    }
+
+    /// Converts `x` into a residue. This takes constant time when `x` is non-negative and has no
+    /// more limbs than the modulus. Anything else is reduced with a variable-time division first.
+    pub fn residue(&self, x: &BigInt) -> Residue {
+        let data = if x.is_negative() || x.data.len() > self.mont.len() {
+            self.mont.encode(x)
+        } else {
+            self.mont.encode_limbs(&x.data)
+        };
+        Residue { data }
+    }
+
+    /// Converts a residue back into a `BigInt` in the range `0..n`.
+    pub fn retrieve(&self, a: &Residue) -> BigInt {
+        self.mont.decode(&a.data)
+    }
+
+    /// The residue of `0`.
+    pub fn zero(&self) -> Residue {
+        Residue {
+            data: vec![0; self.mont.len()],
+        }
+    }
+
+    /// The residue of `1`.
+    pub fn one(&self) -> Residue {
+        Residue {
+            data: self.mont.one(),
+        }
+    }
+
+    /// Calculates `a + b`.
+    pub fn add(&self, a: &Residue, b: &Residue) -> Residue {
+        let mut sum = a.data.clone();
+        let carry = add_limbs(&mut sum, &b.data);
+
+        let mut diff = sum.clone();
+        let borrow = sub_limbs(&mut diff, self.mont.modulus());
+
+        // the sum is below `2n`, so it only needs reducing if it overflowed or is at least `n`
+        select_limbs(&mut diff, &sum, Choice::from(borrow) & !Choice::from(carry));
+        Residue { data: diff }
+    }
+
+    /// Calculates `a - b`.
+    pub fn sub(&self, a: &Residue, b: &Residue) -> Residue {
+        let mut diff = a.data.clone();
+        let borrow = sub_limbs(&mut diff, &b.data);
+
+        let mut wrapped = diff.clone();
+        add_limbs(&mut wrapped, self.mont.modulus());
+
+        select_limbs(&mut diff, &wrapped, Choice::from(borrow));
+        Residue { data: diff }
+    }
+
+    /// Calculates `-a`.
+    pub fn neg(&self, a: &Residue) -> Residue {
+        self.sub(&self.zero(), a)
+    }
+
+    /// Calculates `a * b`.
+    pub fn mul(&self, a: &Residue, b: &Residue) -> Residue {
+        Residue {
+            data: self.mont.mul(&a.data, &b.data),
+        }
+    }
+
+    /// Raises `a` to the power of `exp` with a fixed window, always doing the same sequence of
+    /// multiplications and reading every entry of the window table, so that neither the
+    /// exponent nor the base affects the timing. The exponent is processed as if it had at
+    /// least as many limbs as the modulus. Panics if `exp` is negative.
+    pub fn pow(&self, a: &Residue, exp: &BigInt) -> Residue {
+        assert!(!exp.is_negative(), "attempt to raise to a negative power");
+
+        const BITS: usize = Base::BITS as usize;
+        let mask = (1 << Self::WINDOW) - 1;
+
+        let mut table = vec![self.one()];
+        for i in 1..=mask {
+            table.push(self.mul(&table[i - 1], a));
+        }
+
+        let bits = exp.data.len().max(self.mont.len()) * BITS;
+        let mut acc = self.one();
+
+        for i in (0..bits / Self::WINDOW).rev() {
+            for _ in 0..Self::WINDOW {
+                acc = self.mul(&acc, &acc);
+            }
+
+            let bit = i * Self::WINDOW;
+            let window =
+                exp.data.get(bit / BITS).copied().unwrap_or(0) >> (bit % BITS) & mask as Base;
+
+            let mut entry = table[0].clone();
+            for (j, e) in table.iter().enumerate().skip(1) {
+                entry.conditional_assign(e, (j as Base).ct_eq(&window));
+            }
+            acc = self.mul(&acc, &entry);
+        }
+
+        acc
+    }
+}
+
+impl BigInt {
+    /// Calculates the power of a number within an odd modulus in constant time, using
+    /// `Modulus::pow`. The result is in the range `0..m.abs()`. Panics if `m` is even or `exp` is
+    /// negative.
+    pub fn modpow_ct(&self, exp: &BigInt, m: &BigInt) -> BigInt {
+        let m = Modulus::new(m);
+        m.retrieve(&m.pow(&m.residue(self), exp))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uint::U256;
+
+    // xorshift, so the operands are reproducible without a dependency on an rng
+    fn limbs(len: usize, seed: &mut u64) -> Vec<Base> {
+        (0..len)
+            .map(|_| {
+                *seed ^= *seed << 13;
+                *seed ^= *seed >> 7;
+                *seed ^= *seed << 17;
+                *seed as Base
+            })
+            .collect()
+    }
+
+    #[test]
+    fn choice() {
+        assert!(bool::from(Choice::TRUE));
+        assert!(!bool::from(Choice::FALSE));
+        assert!(bool::from(Choice::from(true) & !Choice::from(false)));
+        assert!(!bool::from(Choice::FALSE | Choice::FALSE));
+        assert!(bool::from(Choice::from_nonzero(1 << (Base::BITS - 1))));
+        assert!(!bool::from(Choice::from_nonzero(0)));
+    }
+
+    #[test]
+    fn primitive() {
+        assert!(bool::from(5u8.ct_eq(&5)));
+        assert!(!bool::from(u128::MAX.ct_eq(&(1 << 127))));
+        assert_eq!(
+            u128::conditional_select(&1, &u128::MAX, Choice::TRUE),
+            u128::MAX
+        );
+        assert_eq!(u32::conditional_select(&1, &2, Choice::FALSE), 1);
+
+        let (mut a, mut b) = (3u64, 4u64);
+        u64::conditional_swap(&mut a, &mut b, Choice::FALSE);
+        assert_eq!((a, b), (3, 4));
+        u64::conditional_swap(&mut a, &mut b, Choice::TRUE);
+        assert_eq!((a, b), (4, 3));
+    }
+
+    #[test]
+    fn uint() {
+        let (mut a, mut b) = (U256::MAX, U256::from(7u8));
+        assert!(bool::from(a.ct_eq(&U256::MAX)));
+        assert!(!bool::from(a.ct_eq(&b)));
+        assert_eq!(U256::conditional_select(&a, &b, Choice::TRUE), b);
+
+        U256::conditional_swap(&mut a, &mut b, Choice::TRUE);
+        assert_eq!((a, b), (U256::from(7u8), U256::MAX));
+        a.conditional_assign(&b, Choice::FALSE);
+        assert_eq!(a, 7u8);
+    }
+
+    #[test]
+    fn residue_arithmetic() {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        for len in [1, 2, 5] {
+            let n = BigInt::from_limbs(limbs(len, &mut seed)) | BigInt::one();
+            let m = Modulus::new(&n);
+            assert_eq!(m.value(), n);
+
+            for _ in 0..20 {
+                let a = BigInt::from_limbs(limbs(len, &mut seed)) % &n;
+                let b = BigInt::from_limbs(limbs(len + 1, &mut seed));
+                let (x, y) = (m.residue(&a), m.residue(&b));
+
+                assert_eq!(m.retrieve(&x), a);
+                assert_eq!(m.retrieve(&m.add(&x, &y)), (&a + &b) % &n);
+                assert_eq!(m.retrieve(&m.sub(&x, &y)), (&a - &b).modulo(&n));
+                assert_eq!(m.retrieve(&m.mul(&x, &y)), (&a * &b) % &n);
+                assert_eq!(m.retrieve(&m.neg(&x)), (BigInt::zero() - &a).modulo(&n));
+                assert!(bool::from(m.add(&x, &m.neg(&x)).ct_eq(&m.zero())));
+            }
+        }
+    }
+
+    #[test]
+    fn unreduced_residue() {
+        // values with as many limbs as the modulus are encoded without reducing them first
+        let n = BigInt::from(0xffff_fffb_u32);
+        let m = Modulus::new(&n);
+        let x = BigInt::from(Base::MAX);
+        assert_eq!(m.retrieve(&m.residue(&x)), &x % &n);
+        assert_eq!(m.retrieve(&m.residue(&BigInt::from(-3))), n - 3u8);
+    }
+
+    #[test]
+    fn pow() {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        for len in [1, 2, 4] {
+            let n = BigInt::from_limbs(limbs(len, &mut seed)) | BigInt::one();
+            for exp_len in [1, len, len + 2] {
+                let a = BigInt::from_limbs(limbs(len, &mut seed));
+                let exp = BigInt::from_limbs(limbs(exp_len, &mut seed));
+                assert_eq!(a.modpow_ct(&exp, &n), a.modpow(&exp, &n), "{a} {exp} {n}");
+            }
+        }
+    }
+
+    #[test]
+    fn pow_small() {
+        let n = BigInt::from(1_000_003);
+        for exp in 0..20u8 {
+            assert_eq!(
+                BigInt::from(3).modpow_ct(&exp.into(), &n),
+                BigInt::from(3).modpow(&exp.into(), &n)
+            );
+        }
+        assert_eq!(BigInt::from(5).modpow_ct(&BigInt::one(), &BigInt::one()), 0);
+    }
+
+    #[test]
+    #[should_panic = "constant-time arithmetic requires an odd modulus"]
+    fn even_modulus() {
+        BigInt::from(3).modpow_ct(&BigInt::from(2), &BigInt::from(10));
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     str::FromStr,
 };
 
-use crate::{Base, BigInt};
+use crate::{Base, BigInt, Limbs};
 
 #[derive(Debug)]
 /// An error generated when trying to parse a string into a `BigInt`.
@@ -30,19 +30,36 @@ impl Display for TryFromBigIntError {
     }
 }
 
+#[derive(Debug)]
+/// An error generated when trying to convert a non-finite float into a `BigInt` or `BigRational`.
+pub struct TryFromFloatError(pub(crate) ());
+
+impl Error for TryFromFloatError {}
+
+impl Display for TryFromFloatError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "cannot convert a non-finite float")
+    }
+}
+
 impl BigInt {
-    /// Converts `self` into bytes in a big-endian order.
+    /// Converts `self` into bytes in a big-endian order, without any leading zeros.
     pub fn to_be_bytes(&self) -> Vec<u8> {
         self.to_le_bytes().into_iter().rev().collect()
     }
 
-    /// Converts `self` into bytes in a little-endian order.
+    /// Converts `self` into bytes in a little-endian order, without any trailing zeros.
     pub fn to_le_bytes(&self) -> Vec<u8> {
-        self.data
-            .clone()
-            .into_iter()
+        let mut bytes = self
+            .data
+            .iter()
             .flat_map(|e| e.to_le_bytes())
-            .collect()
+            .collect::<Vec<_>>();
+
+        while bytes.ends_with(&[0]) && bytes.len() > 1 {
+            bytes.pop();
+        }
+        bytes
     }
 
     /// Converts big-endian bytes into a `BigInt`.
@@ -56,22 +73,89 @@ impl BigInt {
     pub fn from_le_bytes(bytes: &[u8]) -> Self {
         const BYTES: usize = Base::BITS as usize / 8;
 
-        let mut bytes = bytes.to_vec();
-        bytes.append(&mut vec![0; BYTES.saturating_sub(bytes.len()) % BYTES]);
-
-        let mut digits = bytes
+        let digits = bytes
             .chunks(BYTES)
-            .map(|e| Base::from_le_bytes(e.try_into().unwrap()))
-            .collect::<Vec<_>>();
+            .map(|e| {
+                let mut limb = [0; BYTES];
+                limb[..e.len()].copy_from_slice(e);
+                Base::from_le_bytes(limb)
+            })
+            .collect::<Limbs>();
+
+        Self::from_limbs(digits)
+    }
+
+    /// Converts `self` into exactly `len` bytes in a big-endian order, padded with leading zeros,
+    /// or `None` if it does not fit. The sign is ignored, the same as `to_be_bytes`.
+    pub fn to_be_bytes_padded(&self, len: usize) -> Option<Vec<u8>> {
+        let mut bytes = self.to_le_bytes_padded(len)?;
+        bytes.reverse();
+        Some(bytes)
+    }
 
-        while digits.len() > 1 && digits[digits.len() - 1] == 0 {
-            digits.pop();
+    /// Converts `self` into exactly `len` bytes in a little-endian order, padded with trailing
+    /// zeros, or `None` if it does not fit. The sign is ignored, the same as `to_le_bytes`.
+    pub fn to_le_bytes_padded(&self, len: usize) -> Option<Vec<u8>> {
+        if (self.bits() + 7) / 8 > len {
+            return None;
         }
 
-        Self {
-            signed: false,
-            data: digits,
+        let mut bytes = self.to_le_bytes();
+        bytes.resize(len, 0);
+        Some(bytes)
+    }
+
+    /// Converts `self` into the shortest two's complement bytes in a big-endian order, so the
+    /// highest bit of the first byte is the sign.
+    pub fn to_signed_be_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_signed_le_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Converts `self` into the shortest two's complement bytes in a little-endian order, so the
+    /// highest bit of the last byte is the sign.
+    pub fn to_signed_le_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.to_le_bytes();
+        if self.signed {
+            negate_bytes(&mut bytes);
         }
+
+        // add a byte when the top bit does not already hold the sign, and remove redundant ones
+        let fill = if self.signed { 0xff } else { 0 };
+        if bytes.last().map_or(false, |&e| e >> 7 != fill >> 7) {
+            bytes.push(fill);
+        }
+        while bytes.len() > 1
+            && bytes[bytes.len() - 1] == fill
+            && bytes[bytes.len() - 2] >> 7 == fill >> 7
+        {
+            bytes.pop();
+        }
+        bytes
+    }
+
+    /// Converts big-endian two's complement bytes into a `BigInt`, which is negative when the
+    /// highest bit of the first byte is set.
+    pub fn from_signed_be_bytes(bytes: &[u8]) -> Self {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_signed_le_bytes(&bytes)
+    }
+
+    /// Converts little-endian two's complement bytes into a `BigInt`, which is negative when the
+    /// highest bit of the last byte is set.
+    pub fn from_signed_le_bytes(bytes: &[u8]) -> Self {
+        let signed = bytes.last().map_or(false, |&e| e >> 7 == 1);
+        if !signed {
+            return Self::from_le_bytes(bytes);
+        }
+
+        let mut bytes = bytes.to_vec();
+        negate_bytes(&mut bytes);
+        let mut val = Self::from_le_bytes(&bytes);
+        val.signed = true;
+        val
     }
 
     /// Converts a string in a given base to a `BigInt`. Panics if `radix` is not in `2..=36`.
@@ -90,10 +174,8 @@ impl BigInt {
             return Err(ParseBigIntError(()));
         }
 
-        let mut pow = BigInt::one();
-        let mut val = BigInt::zero();
-
-        for c in s.to_ascii_lowercase().chars().rev() {
+        let mut digits = Vec::with_capacity(s.len());
+        for c in s.to_ascii_lowercase().chars() {
             let digit = match c {
                 '0'..='9' => c as u8 - b'0',
                 'a'..='z' => c as u8 - b'a' + 10,
@@ -102,16 +184,158 @@ impl BigInt {
             };
 
             if digit < radix {
-                val += pow.clone() * digit;
-                pow *= radix;
+                digits.push(digit);
             } else {
                 return Err(ParseBigIntError(()));
             }
         }
 
-        val.signed = sign;
+        let mut val = if radix.is_power_of_two() {
+            from_radix_pow2(&digits, radix)
+        } else {
+            let (chunk, len) = radix_chunk(radix);
+
+            // group the digits into limbs from the least significant end, so that only the most
+            // significant group may be short
+            let first = digits.len() % len;
+            let groups = (first > 0)
+                .then(|| &digits[..first])
+                .into_iter()
+                .chain(digits[first..].chunks(len))
+                .map(|e| e.iter().fold(0, |acc, &d| acc * radix as Base + d as Base))
+                .collect::<Vec<_>>();
+
+            let mut pows = vec![BigInt::from(chunk)];
+            while 1 << pows.len() < groups.len() {
+                let pow = &pows[pows.len() - 1];
+                pows.push(pow * pow);
+            }
+            from_radix_chunks(&groups, &pows)
+        };
+
+        val.signed = sign && val != 0;
         Ok(val)
     }
+
+    /// Converts `self` into the nearest `f64`, rounding halfway cases to even. Values too large
+    /// for an `f64` become infinite.
+    pub fn to_f64(&self) -> f64 {
+        let (top, exp) = self.top_bits();
+        let val = if exp >= f64::MAX_EXP as usize {
+            f64::INFINITY
+        } else {
+            // the conversion rounds correctly, since `top` keeps a sticky bit below the ones that
+            // fit, and scaling by a power of two is exact
+            top as f64 * f64::from_bits(((exp + f64::MAX_EXP as usize - 1) as u64) << 52)
+        };
+
+        if self.signed {
+            -val
+        } else {
+            val
+        }
+    }
+
+    /// Converts `self` into the nearest `f32`, rounding halfway cases to even. Values too large
+    /// for an `f32` become infinite.
+    pub fn to_f32(&self) -> f32 {
+        let (top, exp) = self.top_bits();
+        let val = if exp >= f32::MAX_EXP as usize {
+            f32::INFINITY
+        } else {
+            top as f32 * f32::from_bits(((exp + f32::MAX_EXP as usize - 1) as u32) << 23)
+        };
+
+        if self.signed {
+            -val
+        } else {
+            val
+        }
+    }
+
+    // The highest 64 bits of the magnitude and the power of two they are scaled by, with the
+    // lowest bit set if any of the bits below them are, so that rounding to fewer bits is correct.
+    fn top_bits(&self) -> (u64, usize) {
+        let exp = self.bits().saturating_sub(64);
+        let top = (self.abs() >> exp).magnitude().unwrap() as u64;
+        (top | (self.trailing_zeros() < exp) as u64, exp)
+    }
+
+    // The magnitude of `self`, if it fits in a `u128`.
+    fn magnitude(&self) -> Option<u128> {
+        (self.bits() <= 128).then(|| {
+            self.data
+                .iter()
+                .rev()
+                .fold(0, |acc, &e| acc << Base::BITS | e as u128)
+        })
+    }
+}
+
+// Negates little-endian two's complement bytes in place, by inverting them and adding one.
+fn negate_bytes(bytes: &mut [u8]) {
+    let mut carry = true;
+    for e in bytes.iter_mut() {
+        (*e, carry) = (!*e).overflowing_add(carry as u8);
+    }
+}
+
+// Numbers with fewer limbs than this are converted to or from strings one limb at a time, rather
+// than by recursively splitting them in half.
+pub(crate) const RADIX_CONVERSION_THRESHOLD: usize = 32;
+
+// The largest power of `radix` which fits in a limb, and the number of digits it holds.
+pub(crate) fn radix_chunk(radix: u8) -> (Base, usize) {
+    let (mut pow, mut digits) = (radix as Base, 1);
+    while let Some(p) = pow.checked_mul(radix as Base) {
+        pow = p;
+        digits += 1;
+    }
+    (pow, digits)
+}
+
+// Packs the digits of a power of two radix, most significant first, directly into limbs.
+fn from_radix_pow2(digits: &[u8], radix: u8) -> BigInt {
+    const BITS: usize = Base::BITS as usize;
+
+    let width = radix.trailing_zeros() as usize;
+    let mut data = vec![0; (digits.len() * width + BITS - 1) / BITS];
+
+    for (i, &d) in digits.iter().rev().enumerate() {
+        let (l, s) = (i * width / BITS, i * width % BITS);
+        data[l] |= (d as Base) << s;
+        if s + width > BITS {
+            data[l + 1] |= (d as Base) >> (BITS - s);
+        }
+    }
+
+    BigInt::from_limbs(data)
+}
+
+// Combines digits in the base `pows[0]`, most significant first, where `pows[i] = pows[0]^(2^i)`.
+// Long runs are split so the low half holds a power of two digits, and the halves are joined with
+// a single large multiplication.
+fn from_radix_chunks(groups: &[Base], pows: &[BigInt]) -> BigInt {
+    if groups.len() < RADIX_CONVERSION_THRESHOLD {
+        let chunk = pows[0].data[0];
+        let mut data: Vec<Base> = vec![0];
+
+        for &g in groups {
+            let mut carry = g;
+            for x in data.iter_mut() {
+                (*x, carry) = x.carrying_mul(chunk, carry);
+            }
+            if carry != 0 {
+                data.push(carry);
+            }
+        }
+
+        return BigInt::from_limbs(data);
+    }
+
+    let i = (usize::BITS - 1 - (groups.len() - 1).leading_zeros()) as usize;
+    let (high, low) = groups.split_at(groups.len() - (1 << i));
+    from_radix_chunks(high, pows) * &pows[i] + from_radix_chunks(low, pows)
 }
 
 impl FromStr for BigInt {
@@ -157,10 +381,9 @@ macro_rules! impl_from_uint {
                 type Error = TryFromBigIntError;
 
                 fn try_from(i: &BigInt) -> Result<Self, Self::Error> {
-                    if i.bits() <= <$t>::BITS as usize && let Ok(val) = <$t>::try_from(i.data[0]) {
-                        Ok(val)
-                    } else {
-                        Err(TryFromBigIntError(()))
+                    match i.magnitude() {
+                        Some(val) if !i.signed => <$t>::try_from(val).map_err(|_| TryFromBigIntError(())),
+                        _ => Err(TryFromBigIntError(())),
                     }
                 }
             }
@@ -174,7 +397,7 @@ macro_rules! impl_from_int {
             impl From<$t> for BigInt {
                 fn from(num: $t) -> Self {
                     BigInt {
-                        data: BigInt::from_le_bytes(&num.abs().to_le_bytes()).data,
+                        data: BigInt::from(num.unsigned_abs()).data,
                         signed: num.is_negative(),
                     }
                 }
@@ -192,10 +415,11 @@ macro_rules! impl_from_int {
                 type Error = TryFromBigIntError;
 
                 fn try_from(i: &BigInt) -> Result<Self, Self::Error> {
-                    if i.bits() < <$t>::BITS as usize && let Ok(val) = <$t>::try_from(i.data[0] * i.signum() as u64) {
-                        Ok(val)
-                    } else {
-                        Err(TryFromBigIntError(()))
+                    match i.magnitude() {
+                        Some(val) if !i.signed => <$t>::try_from(val).map_err(|_| TryFromBigIntError(())),
+                        // the magnitude of the minimum is one more than the maximum
+                        Some(val) if val <= <$t>::MIN.unsigned_abs() as u128 => Ok((val as $t).wrapping_neg()),
+                        _ => Err(TryFromBigIntError(())),
                     }
                 }
             }
@@ -206,6 +430,65 @@ macro_rules! impl_from_int {
 impl_from_uint!(u8, u16, u32, u64, u128, usize);
 impl_from_int!(i8, i16, i32, i64, i128, isize);
 
+/// Floats are truncated towards zero, the same as an `as` cast to a primitive integer.
+impl TryFrom<f64> for BigInt {
+    type Error = TryFromFloatError;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if !f.is_finite() {
+            return Err(TryFromFloatError(()));
+        }
+
+        let bits = f.to_bits();
+        let exp = (bits >> 52 & 0x7ff) as i64 - 1075;
+        let mantissa = bits & ((1 << 52) - 1) | 1 << 52;
+
+        // subnormals, and every other float below one, truncate to zero
+        let mut val = if exp >= 0 {
+            BigInt::from(mantissa) << exp as usize
+        } else if exp > -53 {
+            BigInt::from(mantissa >> -exp)
+        } else {
+            BigInt::zero()
+        };
+        val.signed = f.is_sign_negative() && val != 0;
+        Ok(val)
+    }
+}
+
+/// Floats are truncated towards zero, the same as an `as` cast to a primitive integer.
+impl TryFrom<f32> for BigInt {
+    type Error = TryFromFloatError;
+
+    fn try_from(f: f32) -> Result<Self, Self::Error> {
+        BigInt::try_from(f as f64)
+    }
+}
+
+impl From<&BigInt> for f64 {
+    fn from(i: &BigInt) -> Self {
+        i.to_f64()
+    }
+}
+
+impl From<BigInt> for f64 {
+    fn from(i: BigInt) -> Self {
+        i.to_f64()
+    }
+}
+
+impl From<&BigInt> for f32 {
+    fn from(i: &BigInt) -> Self {
+        i.to_f32()
+    }
+}
+
+impl From<BigInt> for f32 {
+    fn from(i: BigInt) -> Self {
+        i.to_f32()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -213,17 +496,17 @@ mod tests {
 
     #[test]
     fn it_works() {
-        assert_eq!(BigInt::from(0u8).data, vec![0]);
-        assert_eq!(BigInt::from(1).data, vec![1]);
-        assert_eq!(BigInt::from(Base::MAX).data, vec![Base::MAX]);
-        assert_eq!(BigInt::from(Base::MAX as BaseExt + 1).data, vec![0, 1]);
+        assert_eq!(*BigInt::from(0u8).data, [0]);
+        assert_eq!(*BigInt::from(1).data, [1]);
+        assert_eq!(*BigInt::from(Base::MAX).data, [Base::MAX]);
+        assert_eq!(*BigInt::from(Base::MAX as BaseExt + 1).data, [0, 1]);
         assert_eq!(
-            BigInt::from(Base::MAX as BaseExt * Base::MAX as BaseExt).data,
-            vec![1, Base::MAX - 1]
+            *BigInt::from(Base::MAX as BaseExt * Base::MAX as BaseExt).data,
+            [1, Base::MAX - 1]
         );
         assert_eq!(
-            BigInt::from((Base::MAX as BaseExt + 1) * Base::MAX as BaseExt).data,
-            vec![0, Base::MAX]
+            *BigInt::from((Base::MAX as BaseExt + 1) * Base::MAX as BaseExt).data,
+            [0, Base::MAX]
         );
     }
 
@@ -243,6 +526,13 @@ mod tests {
         );
     }
 
+    #[test]
+    fn to_bytes_minimal() {
+        assert_eq!(BigInt::zero().to_be_bytes(), [0]);
+        assert_eq!(BigInt::from(0x1234).to_be_bytes(), [0x12, 0x34]);
+        assert_eq!(BigInt::from(0x1234).to_le_bytes(), [0x34, 0x12]);
+    }
+
     #[test]
     fn from_str_small() {
         assert_eq!(BigInt::from_str("42").unwrap(), 42,);
@@ -270,13 +560,235 @@ mod tests {
                 .unwrap(),
             BigInt {
                 signed: false,
-                data: vec![
+                data: [
                     0xB177FBA51DB92C2A,
                     0xDF4C2F87EBC0992A,
                     0x3C16C17251B26645,
                     0x77076D0A7318A57D
-                ],
+                ]
+                .into(),
             }
         );
     }
+
+    #[test]
+    fn from_str_radix_round_trip() {
+        let x = (BigInt::from(0x1234_5678_9abc_def0_u64).pow(80u8) - 1u8) * BigInt::from(-1);
+        for radix in 2..=36 {
+            let s = x.to_string_radix(radix);
+            assert_eq!(
+                BigInt::from_str_radix(&s, radix).unwrap(),
+                x,
+                "radix {radix}"
+            );
+        }
+    }
+
+    #[test]
+    fn from_str_radix_long() {
+        let s = "9".repeat(100_000);
+        let x = BigInt::from_str(&s).unwrap();
+        assert_eq!(x, BigInt::from(10).pow(100_000u32) - 1u8);
+        assert_eq!(x.to_string(), s);
+    }
+
+    #[test]
+    fn from_str_radix_pow2() {
+        assert_eq!(BigInt::from_str_radix("1_0000", 2).unwrap(), 16);
+        assert_eq!(BigInt::from_str_radix("-Vv", 32).unwrap(), -(31 * 32 + 31));
+        assert_eq!(
+            BigInt::from_str_radix(&"f".repeat(40), 16).unwrap(),
+            (BigInt::one() << 160u8) - 1u8
+        );
+    }
+
+    #[test]
+    fn from_str_invalid() {
+        assert!(BigInt::from_str("").is_err());
+        assert!(BigInt::from_str("-").is_err());
+        assert!(BigInt::from_str("12a").is_err());
+        assert!(BigInt::from_str_radix("2", 2).is_err());
+        assert_eq!(BigInt::from_str("-0").unwrap().signum(), 0);
+    }
+
+    #[test]
+    fn try_from_bigint() {
+        assert_eq!(i8::try_from(BigInt::from(-128)).ok(), Some(-128));
+        assert!(i8::try_from(BigInt::from(-129)).is_err());
+        assert!(i8::try_from(BigInt::from(128)).is_err());
+        assert_eq!(i64::try_from(BigInt::from(-5)).ok(), Some(-5));
+        assert_eq!(
+            i128::try_from(BigInt::from(i128::MIN)).ok(),
+            Some(i128::MIN)
+        );
+        assert_eq!(
+            i128::try_from(BigInt::from(i128::MAX)).ok(),
+            Some(i128::MAX)
+        );
+        assert!(i128::try_from(BigInt::from(i128::MIN) - 1u8).is_err());
+        assert_eq!(
+            u128::try_from(BigInt::from(u128::MAX)).ok(),
+            Some(u128::MAX)
+        );
+        assert!(u128::try_from(BigInt::one() << 128u8).is_err());
+        assert!(u64::try_from(BigInt::one() << 64u8).is_err());
+        assert!(u8::try_from(BigInt::from(-1)).is_err());
+        assert_eq!(u8::try_from(BigInt::zero()).ok(), Some(0));
+    }
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(BigInt::try_from(0.0).unwrap(), 0);
+        assert_eq!(BigInt::try_from(-0.0).unwrap().signum(), 0);
+        assert_eq!(BigInt::try_from(2.9).unwrap(), 2);
+        assert_eq!(BigInt::try_from(-2.9).unwrap(), -2);
+        assert_eq!(BigInt::try_from(-0.5).unwrap().signum(), 0);
+        assert_eq!(BigInt::try_from(f64::MIN_POSITIVE / 4.0).unwrap(), 0);
+        assert_eq!(
+            BigInt::try_from(1e20).unwrap(),
+            100_000_000_000_000_000_000u128
+        );
+        assert_eq!(
+            BigInt::try_from(2f64.powi(200)).unwrap(),
+            BigInt::one() << 200u8
+        );
+        assert_eq!(BigInt::try_from(-1.5f32).unwrap(), -1);
+        assert!(BigInt::try_from(f64::NAN).is_err());
+        assert!(BigInt::try_from(f64::NEG_INFINITY).is_err());
+        assert!(BigInt::try_from(f32::INFINITY).is_err());
+    }
+
+    #[test]
+    fn to_f64() {
+        let values = [
+            0,
+            1,
+            -1,
+            (1 << 53) + 1,
+            (1 << 54) + 2,
+            (1 << 54) + 6,
+            -((1 << 60) + (1 << 7) + 1),
+            i64::MAX as i128,
+            u64::MAX as i128 + 1,
+            i128::MAX,
+            i128::MIN,
+            0x1234_5678_9abc_def0_1234_5678,
+        ];
+        for x in values {
+            assert_eq!(BigInt::from(x).to_f64(), x as f64, "{x}");
+            assert_eq!(BigInt::from(x).to_f32(), x as f32, "{x}");
+        }
+    }
+
+    #[test]
+    fn to_f64_ties() {
+        // halfway between two floats rounds to even, unless a lower bit breaks the tie
+        let x = (BigInt::one() << 100u8) + (BigInt::one() << 47u8);
+        assert_eq!(x.to_f64(), 2f64.powi(100));
+        let x = (BigInt::one() << 100u8) + (BigInt::one() << 47u8) + 1u8;
+        assert_eq!(x.to_f64(), 2f64.powi(100) + 2f64.powi(48));
+        let x = (BigInt::one() << 100u8) + (BigInt::one() << 48u8) + (BigInt::one() << 47u8);
+        assert_eq!(x.to_f64(), 2f64.powi(100) + 2f64.powi(49));
+    }
+
+    #[test]
+    fn to_f64_limits() {
+        let max = BigInt::try_from(f64::MAX).unwrap();
+        assert_eq!(max.to_f64(), f64::MAX);
+        assert_eq!((BigInt::one() << 1024u16).to_f64(), f64::INFINITY);
+        assert_eq!((BigInt::from(-1) << 5000u16).to_f64(), f64::NEG_INFINITY);
+        // rounds up past the largest float
+        let x = &max + (BigInt::one() << 970u16);
+        assert_eq!(x.to_f64(), f64::INFINITY);
+        assert_eq!((BigInt::one() << 128u8).to_f32(), f32::INFINITY);
+        assert_eq!(BigInt::try_from(f32::MAX).unwrap().to_f32(), f32::MAX);
+    }
+
+    #[test]
+    fn from_bytes_unaligned() {
+        assert_eq!(BigInt::from_le_bytes(&[]), 0);
+        assert_eq!(
+            BigInt::from_le_bytes(&[1; 9]),
+            u128::from_le_bytes([1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0])
+        );
+        assert_eq!(
+            BigInt::from_be_bytes(&[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 2]),
+            0x102
+        );
+    }
+
+    #[test]
+    fn to_bytes_padded() {
+        let x = BigInt::from(0x1234);
+        assert_eq!(x.to_be_bytes_padded(4).unwrap(), [0, 0, 0x12, 0x34]);
+        assert_eq!(x.to_le_bytes_padded(4).unwrap(), [0x34, 0x12, 0, 0]);
+        assert_eq!(x.to_be_bytes_padded(2).unwrap(), [0x12, 0x34]);
+        assert_eq!(x.to_be_bytes_padded(1), None);
+        assert_eq!(BigInt::zero().to_be_bytes_padded(0).unwrap(), []);
+        assert_eq!(BigInt::zero().to_be_bytes_padded(3).unwrap(), [0, 0, 0]);
+
+        let x = (BigInt::one() << 256u16) - 1u8;
+        assert_eq!(x.to_be_bytes_padded(32).unwrap(), [0xff; 32]);
+        assert_eq!(x.to_be_bytes_padded(31), None);
+    }
+
+    macro_rules! test_signed_bytes {
+        ($name:ident: $x:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                let x = BigInt::from($x);
+                let e: &[u8] = &$e;
+                assert_eq!(x.to_signed_be_bytes(), e);
+                assert_eq!(BigInt::from_signed_be_bytes(e), x);
+
+                let mut le = e.to_vec();
+                le.reverse();
+                assert_eq!(x.to_signed_le_bytes(), le);
+                assert_eq!(BigInt::from_signed_le_bytes(&le), x);
+            }
+        };
+    }
+
+    mod signed_bytes {
+        use super::*;
+
+        test_signed_bytes!(zero: 0, [0]);
+
+        test_signed_bytes!(positive: 0x7f, [0x7f]);
+
+        test_signed_bytes!(positive_top_bit: 0x80, [0, 0x80]);
+
+        test_signed_bytes!(minus_one: -1, [0xff]);
+
+        test_signed_bytes!(negative: -0x80, [0x80]);
+
+        test_signed_bytes!(negative_extended: -0x81, [0xff, 0x7f]);
+
+        test_signed_bytes!(negative_limb: -0x1_0000_0000_0000_0000_i128, [0xff, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        test_signed_bytes!(minimum: i64::MIN, [0x80, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn signed_bytes_round_trip() {
+        for x in [
+            i128::MIN,
+            i128::MAX,
+            -0x1234_5678_9abc,
+            0x8000,
+            -0x8000,
+            -0x8001,
+        ] {
+            assert_eq!(BigInt::from(x).to_signed_le_bytes(), {
+                let bytes = x.to_le_bytes();
+                let len = (1..=16)
+                    .find(|&n| (x << (128 - 8 * n)) >> (128 - 8 * n) == x)
+                    .unwrap();
+                bytes[..len].to_vec()
+            });
+        }
+        assert_eq!(BigInt::from_signed_be_bytes(&[0xff, 0xff, 0xfe]), -2);
+        assert_eq!(BigInt::from_signed_be_bytes(&[0, 0, 5]), 5);
+        assert_eq!(BigInt::from_signed_be_bytes(&[]), 0);
+    }
 }
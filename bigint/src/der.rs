@@ -0,0 +1,184 @@
+//! Encoding and decoding of ASN.1 values with the Distinguished Encoding Rules, which is how
+//! integers are stored in key files and certificates.
+//!
+//! Every value is a tag, a length, then that many bytes of contents. Only the definite length
+//! forms are accepted, and lengths must use the fewest bytes possible.
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+use crate::BigInt;
+
+/// The tag of an `INTEGER`.
+pub const INTEGER: u8 = 0x02;
+
+#[derive(Debug)]
+/// An error generated when trying to decode invalid DER.
+pub struct DerError(());
+
+impl Error for DerError {}
+
+impl Display for DerError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid DER encoding")
+    }
+}
+
+/// Appends a tag and the length of the contents that follow it to `out`.
+pub fn write_header(tag: u8, len: usize, out: &mut Vec<u8>) {
+    out.push(tag);
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let bytes = len.to_be_bytes();
+        let skip = len.leading_zeros() as usize / 8;
+        out.push(0x80 | (bytes.len() - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+}
+
+/// Reads the value at the start of `bytes`, returning its tag, its contents and the bytes after
+/// it.
+pub fn read(bytes: &[u8]) -> Result<(u8, &[u8], &[u8]), DerError> {
+    let (&tag, bytes) = bytes.split_first().ok_or(DerError(()))?;
+    let (&first, bytes) = bytes.split_first().ok_or(DerError(()))?;
+
+    let (len, bytes) = if first < 0x80 {
+        (first as usize, bytes)
+    } else {
+        let n = (first & 0x7f) as usize;
+        if n == 0 || n > bytes.len() || n > usize::BITS as usize / 8 {
+            return Err(DerError(()));
+        }
+
+        let (len, bytes) = bytes.split_at(n);
+        let len = len.iter().fold(0, |acc, &e| acc << 8 | e as usize);
+        // the long form is only used when needed, without leading zeros
+        if len < 0x80 || len >> (8 * (n - 1)) == 0 {
+            return Err(DerError(()));
+        }
+        (len, bytes)
+    };
+
+    if len > bytes.len() {
+        return Err(DerError(()));
+    }
+    let (contents, rest) = bytes.split_at(len);
+    Ok((tag, contents, rest))
+}
+
+impl BigInt {
+    /// Encodes `self` as a DER `INTEGER`.
+    pub fn to_der(&self) -> Vec<u8> {
+        let contents = self.to_signed_be_bytes();
+        let mut out = Vec::with_capacity(contents.len() + 10);
+        write_header(INTEGER, contents.len(), &mut out);
+        out.extend_from_slice(&contents);
+        out
+    }
+
+    /// Decodes a DER `INTEGER` from the start of `bytes`, returning it along with the bytes after
+    /// it. The contents must be the shortest two's complement encoding of the value.
+    pub fn from_der(bytes: &[u8]) -> Result<(Self, &[u8]), DerError> {
+        let (tag, contents, rest) = read(bytes)?;
+        if tag != INTEGER || contents.is_empty() {
+            return Err(DerError(()));
+        }
+
+        // a leading byte is redundant if it only repeats the sign bit of the next one
+        if let [a, b, ..] = contents && (*a == 0 && b >> 7 == 0 || *a == 0xff && b >> 7 == 1) {
+            return Err(DerError(()));
+        }
+
+        Ok((Self::from_signed_be_bytes(contents), rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_der {
+        ($name:ident: $x:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                let x = BigInt::from($x);
+                let e: &[u8] = &$e;
+                assert_eq!(x.to_der(), e);
+                let (y, rest) = BigInt::from_der(e).unwrap();
+                assert_eq!(y, x);
+                assert!(rest.is_empty());
+            }
+        };
+    }
+
+    test_der!(zero: 0, [0x02, 0x01, 0x00]);
+
+    test_der!(small: 127, [0x02, 0x01, 0x7f]);
+
+    test_der!(top_bit: 128, [0x02, 0x02, 0x00, 0x80]);
+
+    test_der!(two_bytes: 256, [0x02, 0x02, 0x01, 0x00]);
+
+    test_der!(minus_one: -1, [0x02, 0x01, 0xff]);
+
+    test_der!(negative: -128, [0x02, 0x01, 0x80]);
+
+    test_der!(negative_extended: -129, [0x02, 0x02, 0xff, 0x7f]);
+
+    #[test]
+    fn long_length() {
+        // 2^1023 needs 129 bytes with the leading zero
+        let x = BigInt::one() << 1023u16;
+        let der = x.to_der();
+        assert_eq!(der[..4], [0x02, 0x81, 0x81, 0x00]);
+        assert_eq!(der.len(), 3 + 129);
+        assert_eq!(BigInt::from_der(&der).unwrap().0, x);
+
+        let x = BigInt::one() << 4000u16;
+        let der = x.to_der();
+        assert_eq!(der[..4], [0x02, 0x82, 0x01, 0xf5]);
+        assert_eq!(BigInt::from_der(&der).unwrap().0, x);
+    }
+
+    #[test]
+    fn rest() {
+        let mut bytes = BigInt::from(5).to_der();
+        bytes.extend(BigInt::from(-300).to_der());
+        let (a, rest) = BigInt::from_der(&bytes).unwrap();
+        let (b, rest) = BigInt::from_der(rest).unwrap();
+        assert_eq!((a, b), (BigInt::from(5), BigInt::from(-300)));
+        assert!(rest.is_empty());
+    }
+
+    macro_rules! test_der_invalid {
+        ($name:ident: $bytes:expr) => {
+            #[test]
+            fn $name() {
+                assert!(BigInt::from_der(&$bytes).is_err());
+            }
+        };
+    }
+
+    mod invalid {
+        use super::*;
+
+        test_der_invalid!(empty: []);
+
+        test_der_invalid!(wrong_tag: [0x04, 0x01, 0x00]);
+
+        test_der_invalid!(no_contents: [0x02, 0x00]);
+
+        test_der_invalid!(truncated: [0x02, 0x02, 0x01]);
+
+        test_der_invalid!(redundant_zero: [0x02, 0x02, 0x00, 0x7f]);
+
+        test_der_invalid!(redundant_ones: [0x02, 0x02, 0xff, 0x80]);
+
+        test_der_invalid!(indefinite_length: [0x02, 0x80, 0x01, 0x00, 0x00]);
+
+        test_der_invalid!(long_form_short_length: [0x02, 0x81, 0x01, 0x05]);
+
+        test_der_invalid!(length_leading_zero: [0x02, 0x82, 0x00, 0x81]);
+    }
+}
@@ -0,0 +1,534 @@
+//! Integer factorization.
+//!
+//! `factor` removes small primes by trial division, then splits what is left with Pollard's p-1
+//! method, Pollard's rho method using Brent's cycle detection, and finally Lenstra's elliptic
+//! curve method with increasing bounds, until every factor passes the Baillie-PSW primality test.
+//!
+//! The work done is measured in modular multiplications. `factor_with_budget` gives up on a
+//! composite once its budget is spent, so the time taken on hard inputs can be bounded.
+
+use crate::BigInt;
+
+// Primes below this are removed by trial division.
+const TRIAL_LIMIT: u32 = 1 << 16;
+
+// The stage one bound for Pollard's p-1, which costs about `1.44 * P1_BOUND` multiplications.
+const P1_BOUND: u64 = 10_000;
+
+// The most multiplications spent on Pollard's rho, which finds factors up to about 10 digits.
+const RHO_MULTIPLICATIONS: u64 = 1 << 18;
+
+// Stage one bounds and numbers of curves for the elliptic curve method, from GMP-ECM's
+// recommendations for factors of 15, 20, 25, 30 and 35 digits. The bound keeps doubling after.
+const ECM_SCHEDULE: [(u64, u32); 5] = [
+    (2_000, 25),
+    (11_000, 90),
+    (50_000, 300),
+    (250_000, 700),
+    (1_000_000, 1800),
+];
+
+/// The result of factoring an integer within a budget.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Factorization {
+    /// The prime factors and their multiplicities, in increasing order.
+    pub primes: Vec<(BigInt, u32)>,
+    /// The factors which could not be split before the budget ran out, and their multiplicities,
+    /// in increasing order. Each of these is composite, but they may share prime factors.
+    pub composites: Vec<(BigInt, u32)>,
+}
+
+impl Factorization {
+    /// Returns true if every factor is prime.
+    pub fn is_complete(&self) -> bool {
+        self.composites.is_empty()
+    }
+}
+
+// The remaining number of modular multiplications.
+struct Budget(u64);
+
+impl Budget {
+    // Takes `n` multiplications from the budget, or returns false if there are not enough.
+    fn spend(&mut self, n: u64) -> bool {
+        let enough = self.0 >= n;
+        self.0 = self.0.saturating_sub(n);
+        enough
+    }
+
+    // Runs `f` with at most `n` of the remaining multiplications.
+    fn within<T>(&mut self, n: u64, f: impl FnOnce(&mut Budget) -> T) -> T {
+        let mut part = Budget(self.0.min(n));
+        let start = part.0;
+        let t = f(&mut part);
+        self.0 -= start - part.0;
+        t
+    }
+}
+
+// The primes up to and including `limit`, with the sieve of Eratosthenes.
+pub(crate) fn primes_to(limit: u64) -> Vec<u64> {
+    let limit = limit as usize;
+    let mut composite = vec![false; limit + 1];
+    let mut primes = vec![];
+
+    for i in 2..=limit {
+        if !composite[i] {
+            primes.push(i as u64);
+            for j in (i * i..=limit).step_by(i) {
+                composite[j] = true;
+            }
+        }
+    }
+    primes
+}
+
+// The largest power of the prime `q` which is at most `bound`.
+fn prime_power(q: u64, bound: u64) -> u64 {
+    let mut qe = q;
+    while qe <= bound / q {
+        qe *= q;
+    }
+    qe
+}
+
+// Returns `g` if it is a proper factor of `n`.
+fn proper(g: BigInt, n: &BigInt) -> Option<BigInt> {
+    (g != 1 && g != *n).then_some(g)
+}
+
+// Sorts factors, combining the multiplicities of equal ones.
+fn merge(mut factors: Vec<(BigInt, u32)>) -> Vec<(BigInt, u32)> {
+    factors.sort();
+
+    let mut merged: Vec<(BigInt, u32)> = vec![];
+    for (f, e) in factors {
+        match merged.last_mut() {
+            Some((g, k)) if *g == f => *k += e,
+            _ => merged.push((f, e)),
+        }
+    }
+    merged
+}
+
+/// Divides out the primes below `limit` from `n`. Returns them with their multiplicities in
+/// increasing order, along with the remaining cofactor, which is not negative.
+pub fn trial_division(n: &BigInt, limit: u32) -> (Vec<(BigInt, u32)>, BigInt) {
+    let mut n = n.abs();
+    let mut factors = vec![];
+
+    // no prime factor above the square root needs to be found
+    let root = u64::try_from(n.sqrt()).unwrap_or(u64::MAX);
+    for q in primes_to(root.min(limit.saturating_sub(1) as u64)) {
+        if n < q * q {
+            break;
+        }
+
+        let mut e = 0;
+        while n.rem_limb(q as _) == 0 {
+            n.div_rem_limb(q as _);
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((BigInt::from(q), e));
+        }
+    }
+
+    // what is left is prime when it is below the square of the limit
+    if n > 1 && n < (limit as u64).pow(2) {
+        factors.push((n, 1));
+        n = BigInt::one();
+    }
+    (factors, n)
+}
+
+/// Looks for a proper factor of `n` with Pollard's rho method, using Brent's cycle detection,
+/// taking at most `iterations` steps. The factor found is not necessarily prime, and `0`, `1` and
+/// `-1` have none.
+pub fn pollard_rho(n: &BigInt, iterations: u64) -> Option<BigInt> {
+    let n = n.abs();
+    if n <= 1 {
+        return None;
+    }
+    rho(&n, &mut Budget(iterations.saturating_mul(2)))
+}
+
+/// Looks for a proper factor of `n` with Pollard's p-1 method, which finds a prime factor `p`
+/// when every prime power dividing `p - 1` is at most `bound`. The factor found is not necessarily
+/// prime, and `0`, `1` and `-1` have none.
+pub fn pollard_p1(n: &BigInt, bound: u64) -> Option<BigInt> {
+    let n = n.abs();
+    if n <= 1 {
+        return None;
+    }
+    p1(&n, bound, &mut Budget(u64::MAX))
+}
+
+/// Looks for a proper factor of `n` with stage one of Lenstra's elliptic curve method on up to
+/// `curves` curves, which finds a prime factor `p` when the order of one of the curves modulo `p`
+/// only has prime power factors of at most `b1`. The factor found is not necessarily prime, and
+/// `0`, `1` and `-1` have none.
+pub fn ecm(n: &BigInt, b1: u64, curves: u32) -> Option<BigInt> {
+    let n = n.abs();
+    if n <= 1 {
+        return None;
+    }
+    let primes = primes_to(b1);
+    let mut budget = Budget(u64::MAX);
+
+    (0..curves as u64).find_map(|i| ecm_curve(&n, i + 6, &primes, b1, &mut budget))
+}
+
+/// Factors `n` into primes, returning them with their multiplicities in increasing order. The
+/// sign of `n` is ignored, and `1` has no factors. Panics if `n` is `0`.
+///
+/// There is no limit on the work done, so this can take a very long time if `n` has two or more
+/// large prime factors.
+pub fn factor(n: &BigInt) -> Vec<(BigInt, u32)> {
+    factor_with_budget(n, u64::MAX).primes
+}
+
+/// Factors `n` as far as possible using at most about `budget` modular multiplications. The sign
+/// of `n` is ignored, and `1` has no factors. Panics if `n` is `0`.
+pub fn factor_with_budget(n: &BigInt, budget: u64) -> Factorization {
+    assert!(*n != 0, "cannot factor zero");
+
+    let mut budget = Budget(budget);
+    let (mut primes, cofactor) = trial_division(n, TRIAL_LIMIT);
+    let mut composites = vec![];
+
+    let mut stack = vec![(cofactor, 1)];
+    while let Some((m, e)) = stack.pop() {
+        if m == 1 {
+            continue;
+        }
+
+        if m.is_probable_prime_bpsw() {
+            primes.push((m, e));
+        } else if let Some((b, k)) = m.perfect_power() {
+            stack.push((b, e * k));
+        } else if let Some(d) = split(&m, &mut budget) {
+            stack.push((&m / &d, e));
+            stack.push((d, e));
+        } else {
+            composites.push((m, e));
+        }
+    }
+
+    Factorization {
+        primes: merge(primes),
+        composites: merge(composites),
+    }
+}
+
+// Looks for a proper factor of the composite `n`, trying the cheaper methods first.
+fn split(n: &BigInt, budget: &mut Budget) -> Option<BigInt> {
+    if let Some(d) = p1(n, P1_BOUND, budget) {
+        return Some(d);
+    }
+    if let Some(d) = budget.within(RHO_MULTIPLICATIONS, |b| rho(n, b)) {
+        return Some(d);
+    }
+
+    let mut sigma = 6;
+    let (mut b1, mut curves) = ECM_SCHEDULE[0];
+    for i in 1.. {
+        let primes = primes_to(b1);
+        for _ in 0..curves {
+            if let Some(d) = ecm_curve(n, sigma, &primes, b1, budget) {
+                return Some(d);
+            }
+            if budget.0 == 0 {
+                return None;
+            }
+            sigma += 1;
+        }
+
+        (b1, curves) = match ECM_SCHEDULE.get(i) {
+            Some(&next) => next,
+            None => (b1 * 2, curves),
+        };
+    }
+    None
+}
+
+// Pollard's rho with `f(x) = x^2 + c` for increasing `c`, using Brent's cycle detection and
+// taking the gcd of a product of differences to save on gcds.
+fn rho(n: &BigInt, budget: &mut Budget) -> Option<BigInt> {
+    const BATCH: u64 = 128;
+
+    if n.is_even() {
+        return proper(BigInt::from(2), n);
+    }
+    if n.is_probable_prime_bpsw() {
+        return None;
+    }
+
+    // each polynomial spends some of the budget, so this ends once it runs out
+    for c in 1u64.. {
+        let f = |x: &BigInt| (x * x + c) % n;
+
+        let (mut x, mut y, mut ys) = (BigInt::from(2), BigInt::from(2), BigInt::from(2));
+        let (mut q, mut g) = (BigInt::one(), BigInt::one());
+        let mut r = 1;
+
+        while g == 1 {
+            x = y.clone();
+            for _ in 0..r {
+                y = f(&y);
+            }
+            if !budget.spend(r) {
+                return None;
+            }
+
+            let mut k = 0;
+            while k < r && g == 1 {
+                ys = y.clone();
+                let steps = BATCH.min(r - k);
+                for _ in 0..steps {
+                    y = f(&y);
+                    q = q * (&x - &y).abs() % n;
+                }
+                if !budget.spend(2 * steps) {
+                    return None;
+                }
+
+                g = q.gcd(n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+
+        // the batch overshot, so step through it again one difference at a time
+        if g == *n {
+            loop {
+                ys = f(&ys);
+                g = (&x - &ys).abs().gcd(n);
+                if g != 1 {
+                    break;
+                }
+            }
+        }
+
+        if g != *n {
+            return Some(g);
+        }
+    }
+    None
+}
+
+// Stage one of Pollard's p-1, which raises 2 to every prime power up to `bound`.
+fn p1(n: &BigInt, bound: u64, budget: &mut Budget) -> Option<BigInt> {
+    let primes = primes_to(bound);
+    let mut a = BigInt::from(2);
+
+    for (i, &q) in primes.iter().enumerate() {
+        let qe = prime_power(q, bound);
+        if !budget.spend(2 * (u64::BITS - qe.leading_zeros()) as u64) {
+            return None;
+        }
+        a = a.modpow(&BigInt::from(qe), n);
+
+        if i % 64 == 63 || i == primes.len() - 1 {
+            let g = (a.clone() - 1u8).gcd(n);
+            if g != 1 {
+                return proper(g, n);
+            }
+        }
+    }
+    None
+}
+
+// A point `(X : Z)` on a Montgomery curve, without its y coordinate.
+type Point = (BigInt, BigInt);
+
+// Doubles a point, with `a24 = (A + 2) / 4` for the curve `By^2 = x^3 + Ax^2 + x`.
+fn xdbl((x, z): &Point, a24: &BigInt, n: &BigInt) -> Point {
+    let s = (x + z) * (x + z) % n;
+    let d = (x - z) * (x - z) % n;
+    let t = (&s + n - &d) % n;
+    (&s * &d % n, (&d + a24 * &t % n) * t % n)
+}
+
+// Adds two points, given their difference.
+fn xadd((xp, zp): &Point, (xq, zq): &Point, (xd, zd): &Point, n: &BigInt) -> Point {
+    let u = (xp - zp) * (xq + zq) % n;
+    let v = (xp + zp) * (xq - zq) % n;
+    let (s, d) = (&u + &v, u - v);
+    (zd * (&s * &s % n) % n, xd * (&d * &d % n) % n)
+}
+
+// Multiplies a point by `k` with the Montgomery ladder.
+fn ladder(p: &Point, k: u64, a24: &BigInt, n: &BigInt) -> Point {
+    let (mut r0, mut r1) = (p.clone(), xdbl(p, a24, n));
+    for i in (0..(u64::BITS - 1 - k.leading_zeros())).rev() {
+        if k >> i & 1 == 1 {
+            r0 = xadd(&r0, &r1, p, n);
+            r1 = xdbl(&r1, a24, n);
+        } else {
+            r1 = xadd(&r0, &r1, p, n);
+            r0 = xdbl(&r0, a24, n);
+        }
+    }
+    r0
+}
+
+// Stage one of the elliptic curve method on the curve from Suyama's parametrization with `sigma`,
+// which multiplies a point by every prime power up to `b1`.
+fn ecm_curve(
+    n: &BigInt,
+    sigma: u64,
+    primes: &[u64],
+    b1: u64,
+    budget: &mut Budget,
+) -> Option<BigInt> {
+    let sigma = BigInt::from(sigma);
+    let u = (&sigma * &sigma - 5u8).modulo(n);
+    let v = (sigma << 2u8) % n;
+
+    let cube = |x: &BigInt| x * x % n * x % n;
+    let p = (cube(&u), cube(&v));
+
+    // (A + 2) / 4 = (v - u)^3 (3u + v) / (16 u^3 v), and a failed inverse may give a factor
+    let num = cube(&(&v - &u).modulo(n)) * (u * 3u8 + &v) % n;
+    let den = ((&p.0 * &v) << 4u8) % n;
+    let a24 = match den.mod_inverse(n) {
+        Some(inv) => num * inv % n,
+        None => return proper(den.gcd(n), n),
+    };
+
+    let mut p = p;
+    for &q in primes {
+        let qe = prime_power(q, b1);
+        if !budget.spend(10 * (u64::BITS - qe.leading_zeros()) as u64) {
+            return None;
+        }
+        p = ladder(&p, qe, &a24, n);
+    }
+    proper(p.1.gcd(n), n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn factors(f: &[(u64, u32)]) -> Vec<(BigInt, u32)> {
+        f.iter().map(|&(p, e)| (BigInt::from(p), e)).collect()
+    }
+
+    fn product(f: &[(BigInt, u32)]) -> BigInt {
+        f.iter().fold(BigInt::one(), |acc, (p, e)| acc * p.pow(*e))
+    }
+
+    macro_rules! test_factor {
+        ($name:ident: $n:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!(factor(&BigInt::from($n)), factors(&$e));
+            }
+        };
+    }
+
+    test_factor!(one: 1, []);
+
+    test_factor!(prime: 65_537, [(65_537, 1)]);
+
+    test_factor!(small: 360, [(2, 3), (3, 2), (5, 1)]);
+
+    test_factor!(negative: -12, [(2, 2), (3, 1)]);
+
+    test_factor!(fermat_6: (1u128 << 64) + 1, [(274_177, 1), (67_280_421_310_721, 1)]);
+
+    test_factor!(
+        power: 920_096_753u128.pow(3) * 9_973,
+        [(9_973, 1), (920_096_753, 3)]
+    );
+
+    test_factor!(
+        rho: 920_096_753u128 * 6_969_183_373,
+        [(920_096_753, 1), (6_969_183_373, 1)]
+    );
+
+    #[test]
+    fn against_naive() {
+        for n in 2..3000u64 {
+            let f = factor(&BigInt::from(n));
+            assert_eq!(product(&f), n);
+            assert!(f.windows(2).all(|w| w[0].0 < w[1].0), "{n}");
+            for (p, _) in f {
+                let p = u64::try_from(p).unwrap();
+                assert!((2..).take_while(|d| d * d <= p).all(|d| p % d != 0), "{n}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic = "cannot factor zero"]
+    fn zero() {
+        factor(&BigInt::zero());
+    }
+
+    #[test]
+    fn no_proper_factors() {
+        for n in [0, 1, -1] {
+            let n = BigInt::from(n);
+            assert_eq!(super::pollard_rho(&n, u64::MAX), None, "{n}");
+            assert_eq!(super::pollard_p1(&n, 1000), None, "{n}");
+            assert_eq!(super::ecm(&n, 1000, 10), None, "{n}");
+        }
+    }
+
+    #[test]
+    fn trial_division() {
+        let n = BigInt::from(2u64.pow(10) * 3 * 1_000_003);
+        let (f, rest) = super::trial_division(&n, 100);
+        assert_eq!(f, factors(&[(2, 10), (3, 1)]));
+        assert_eq!(rest, 1_000_003);
+
+        let (f, rest) = super::trial_division(&BigInt::from(2 * 97), 100);
+        assert_eq!(f, factors(&[(2, 1), (97, 1)]));
+        assert_eq!(rest, 1);
+    }
+
+    #[test]
+    fn pollard_rho() {
+        let n = BigInt::from(920_096_753u64) * 6_969_183_373u64;
+        let d = super::pollard_rho(&n, 1 << 20).unwrap();
+        assert!(d == 920_096_753u64 || d == 6_969_183_373u64);
+        assert_eq!(super::pollard_rho(&n, 10), None);
+        assert_eq!(super::pollard_rho(&BigInt::from(7), 1_000_000), None);
+        assert_eq!(
+            super::pollard_rho(&((BigInt::one() << 127u8) - 1u8), u64::MAX),
+            None
+        );
+    }
+
+    #[test]
+    fn pollard_p1() {
+        // p - 1 only has factors up to 113, so p is found while q is much too large for rho
+        let p = BigInt::from(2_708_824_364_908_323_091u64);
+        let q = "4169719852946356622268997".parse::<BigInt>().unwrap();
+        let n = &p * &q;
+        assert_eq!(super::pollard_p1(&n, 10_000), Some(p));
+        assert_eq!(super::pollard_p1(&n, 10), None);
+    }
+
+    #[test]
+    fn ecm() {
+        let p = BigInt::from(6_969_183_373u64);
+        let q = "710976765307970877322562273443".parse::<BigInt>().unwrap();
+        assert_eq!(super::ecm(&(&p * &q), 2_000, 100), Some(p));
+    }
+
+    #[test]
+    fn budget() {
+        let p = "4169719852946356622268997".parse::<BigInt>().unwrap();
+        let q = "710976765307970877322562273443".parse::<BigInt>().unwrap();
+        let n = &p * &q * 12u8;
+
+        let f = factor_with_budget(&n, 10_000);
+        assert!(!f.is_complete());
+        assert_eq!(f.primes, factors(&[(2, 2), (3, 1)]));
+        assert_eq!(f.composites, vec![(p * q, 1)]);
+    }
+}
@@ -0,0 +1,1045 @@
+//! Arbitrary precision binary floating point numbers.
+//!
+//! A `BigFloat` is `mantissa * 2^exponent`, where the mantissa has at most as many bits as the
+//! precision of the value. Arithmetic on two values uses the larger of their precisions and the
+//! rounding mode of the left operand. The basic operations and `sqrt` are correctly rounded, while
+//! `exp`, `ln` and `pi` are calculated with extra guard bits before rounding, so are accurate to
+//! within one unit in the last place.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter, LowerExp, UpperExp};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::rational::{BigRational, TryFromFloatError};
+use crate::BigInt;
+
+/// How results which cannot be represented exactly are rounded, as defined by IEEE 754.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Rounding {
+    /// Round to the nearest value, choosing the even mantissa when exactly halfway.
+    #[default]
+    TiesToEven,
+    /// Round to the nearest value, choosing the larger magnitude when exactly halfway.
+    TiesToAway,
+    /// Round towards zero.
+    TowardZero,
+    /// Round towards positive infinity.
+    TowardPositive,
+    /// Round towards negative infinity.
+    TowardNegative,
+}
+
+/// A binary floating point number with a configurable precision and rounding mode.
+#[derive(Clone, Debug)]
+pub struct BigFloat {
+    // always odd, or zero with an exponent of zero, so each value has one representation
+    mantissa: BigInt,
+    exp: i64,
+    prec: usize,
+    rounding: Rounding,
+}
+
+#[derive(Debug)]
+/// An error generated when trying to parse a string into a `BigFloat`.
+pub struct ParseBigFloatError(());
+
+impl Error for ParseBigFloatError {}
+
+impl Display for ParseBigFloatError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid float value")
+    }
+}
+
+// Shifts the magnitude of `x` right, so the result is truncated towards zero.
+fn shr_abs(x: &BigInt, n: usize) -> BigInt {
+    let mut y = x.abs() >> n;
+    y.signed = x.signed && y != 0;
+    y
+}
+
+// `atan(1 / n)` with `w` fractional bits.
+fn atan_inv(n: u32, w: usize) -> BigInt {
+    let n2 = BigInt::from(n * n);
+    let mut term = (BigInt::one() << w) / BigInt::from(n);
+    let mut sum = BigInt::zero();
+
+    for k in 0u32.. {
+        if term == 0 {
+            break;
+        }
+        if k % 2 == 0 {
+            sum += &term / BigInt::from(2 * k + 1);
+        } else {
+            sum -= &term / BigInt::from(2 * k + 1);
+        }
+        term /= &n2;
+    }
+    sum
+}
+
+// `ln(2) = 2 * atanh(1 / 3)` with `w` fractional bits.
+fn ln2_fixed(w: usize) -> BigInt {
+    let mut term = (BigInt::one() << w) / 3u8;
+    let mut sum = BigInt::zero();
+
+    for k in 0u32.. {
+        if term == 0 {
+            break;
+        }
+        sum += &term / BigInt::from(2 * k + 1);
+        term /= 9u8;
+    }
+    sum << 1u8
+}
+
+// `floor(e * log10(2))`, give or take one, using enough digits of `log10(2)` for any `i64`.
+fn log10_pow2(e: i64) -> i64 {
+    const SCALE: i128 = 1_000_000_000_000_000_000;
+    const HI: i128 = 301_029_995_663_981_195;
+    const LO: i128 = 213_738_894_724_493_026;
+
+    let e = e as i128;
+    ((e * HI + (e * LO).div_euclid(SCALE)).div_euclid(SCALE)) as i64
+}
+
+// Decimal exponents up to this size are applied exactly when parsing and printing.
+const EXACT_POW10_LIMIT: u64 = 10_000;
+
+// `10^n` to `prec` bits by repeated squaring, for powers too large to calculate exactly. Each
+// squaring doubles the relative error, so it carries enough guard bits to cover `n`.
+fn pow10_approx(n: u64, prec: usize) -> BigFloat {
+    let w = prec + 16 + 64 - n.leading_zeros() as usize;
+    let ten = BigFloat::from_int(10, w);
+    let mut acc = BigFloat::one(w);
+
+    for i in (0..64 - n.leading_zeros()).rev() {
+        acc = &acc * &acc;
+        if n >> i & 1 == 1 {
+            acc *= &ten;
+        }
+    }
+    acc
+}
+
+impl BigFloat {
+    /// The precision used when parsing strings with `FromStr`, unless more digits are given.
+    pub const DEFAULT_PRECISION: usize = 128;
+
+    // Rounds `mantissa * 2^exp` to `prec` bits. `sticky` means the exact value has a slightly
+    // larger magnitude than `mantissa`, with some non-zero bits below it.
+    fn from_parts(
+        mantissa: BigInt,
+        exp: i64,
+        sticky: bool,
+        prec: usize,
+        rounding: Rounding,
+    ) -> Self {
+        assert!(prec > 0, "precision must be at least one bit");
+
+        let neg = mantissa.is_negative();
+        let mut mag = mantissa.abs();
+        let mut exp = exp;
+
+        let shift = mag.bits().saturating_sub(prec);
+        if shift > 0 || sticky {
+            let dropped = &mag - ((mag.clone() >> shift) << shift);
+            mag >>= shift;
+            exp += shift as i64;
+
+            // where the discarded part lies relative to half of the last place
+            let half = if shift == 0 {
+                Ordering::Less
+            } else {
+                dropped
+                    .cmp(&(BigInt::one() << (shift - 1)))
+                    .then(if sticky {
+                        Ordering::Greater
+                    } else {
+                        Ordering::Equal
+                    })
+            };
+            let inexact = sticky || dropped != 0;
+
+            let up = match rounding {
+                Rounding::TiesToEven => half.is_gt() || half.is_eq() && mag.is_odd(),
+                Rounding::TiesToAway => half.is_ge(),
+                Rounding::TowardZero => false,
+                Rounding::TowardPositive => inexact && !neg,
+                Rounding::TowardNegative => inexact && neg,
+            };
+            if up {
+                mag += 1u8;
+            }
+        }
+
+        let tz = mag.trailing_zeros();
+        if mag == 0 {
+            exp = 0;
+        } else if tz > 0 {
+            mag >>= tz;
+            exp += tz as i64;
+        }
+
+        mag.signed = neg && mag != 0;
+        Self {
+            mantissa: mag,
+            exp,
+            prec,
+            rounding,
+        }
+    }
+
+    // Rounds `num / den * 2^exp` to `prec` bits.
+    fn from_ratio(num: BigInt, exp: i64, den: &BigInt, prec: usize, rounding: Rounding) -> Self {
+        // enough bits for the quotient to have two more bits than the result
+        let shift = (prec + 2 + den.bits()).saturating_sub(num.bits());
+        let (quot, rem) = (num << shift).div_rem(den.clone());
+        Self::from_parts(quot, exp - shift as i64, rem != 0, prec, rounding)
+    }
+
+    /// Creates a `BigFloat` with the value of `0` and a precision of `prec` bits.
+    pub fn zero(prec: usize) -> Self {
+        Self::from_parts(BigInt::zero(), 0, false, prec, Rounding::default())
+    }
+
+    /// Creates a `BigFloat` with the value of `1` and a precision of `prec` bits.
+    pub fn one(prec: usize) -> Self {
+        Self::from_parts(BigInt::one(), 0, false, prec, Rounding::default())
+    }
+
+    /// Rounds the integer `i` to `prec` bits.
+    pub fn from_int(i: impl Into<BigInt>, prec: usize) -> Self {
+        Self::from_parts(i.into(), 0, false, prec, Rounding::default())
+    }
+
+    /// Rounds the rational `r` to `prec` bits.
+    pub fn from_rational(r: &BigRational, prec: usize) -> Self {
+        Self::from_ratio(r.numer().clone(), 0, r.denom(), prec, Rounding::default())
+    }
+
+    /// Rounds the float `f` to `prec` bits. Fails if `f` is not finite.
+    pub fn from_f64(f: f64, prec: usize) -> Result<Self, TryFromFloatError> {
+        Ok(Self::from_rational(&BigRational::try_from(f)?, prec))
+    }
+
+    /// The number of bits needed to hold `digits` decimal digits.
+    pub fn precision_for_digits(digits: usize) -> usize {
+        // slightly more than `log2(10)`
+        digits * 3322 / 1000 + 1
+    }
+
+    /// The precision of the value in bits.
+    pub fn precision(&self) -> usize {
+        self.prec
+    }
+
+    /// The rounding mode used by operations on the value.
+    pub fn rounding(&self) -> Rounding {
+        self.rounding
+    }
+
+    /// The signed mantissa, which is odd unless the value is zero.
+    pub fn mantissa(&self) -> &BigInt {
+        &self.mantissa
+    }
+
+    /// The power of two which the mantissa is multiplied by.
+    pub fn exponent(&self) -> i64 {
+        self.exp
+    }
+
+    /// Rounds the value to a new precision, using its own rounding mode.
+    pub fn with_precision(self, prec: usize) -> Self {
+        Self::from_parts(self.mantissa, self.exp, false, prec, self.rounding)
+    }
+
+    /// Changes the rounding mode used by operations on the value.
+    pub fn with_rounding(mut self, rounding: Rounding) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Returns true if `self` is `0`.
+    pub fn is_zero(&self) -> bool {
+        self.mantissa == 0
+    }
+
+    /// Returns true if `self` is greater than `0`.
+    pub fn is_positive(&self) -> bool {
+        self.mantissa.is_positive()
+    }
+
+    /// Returns true if `self` is less than `0`.
+    pub fn is_negative(&self) -> bool {
+        self.mantissa.is_negative()
+    }
+
+    /// Returns the absolute value of `self`.
+    pub fn abs(&self) -> Self {
+        let mut x = self.clone();
+        x.mantissa.signed = false;
+        x
+    }
+
+    // The value is within `2^(top - 1)..2^top`.
+    fn top(&self) -> i64 {
+        self.exp + self.mantissa.bits() as i64
+    }
+
+    // The value multiplied by `2^w`, truncated towards zero.
+    fn to_fixed(&self, w: usize) -> BigInt {
+        let shift = self.exp + w as i64;
+        if shift >= 0 {
+            self.mantissa.clone() << shift as usize
+        } else {
+            shr_abs(&self.mantissa, (-shift) as usize)
+        }
+    }
+
+    /// Converts the value into an exact fraction.
+    pub fn to_rational(&self) -> BigRational {
+        if self.exp >= 0 {
+            BigRational::from_integer(self.mantissa.clone() << self.exp as usize)
+        } else {
+            BigRational::new(self.mantissa.clone(), BigInt::one() << (-self.exp) as usize)
+        }
+    }
+
+    /// Converts the value into the nearest `f64`, rounding halfway cases to even.
+    pub fn to_f64(&self) -> f64 {
+        let sign = if self.is_negative() { -1.0 } else { 1.0 };
+        if self.is_zero() {
+            0.0
+        } else if self.top() > f64::MAX_EXP as i64 + 1 {
+            sign * f64::INFINITY
+        } else if self.top() < f64::MIN_EXP as i64 - f64::MANTISSA_DIGITS as i64 - 2 {
+            sign * 0.0
+        } else {
+            self.to_rational().to_f64()
+        }
+    }
+
+    fn add_rounded(&self, other: &Self, prec: usize, rounding: Rounding) -> Self {
+        if other.is_zero() {
+            return Self::from_parts(self.mantissa.clone(), self.exp, false, prec, rounding);
+        } else if self.is_zero() {
+            return Self::from_parts(other.mantissa.clone(), other.exp, false, prec, rounding);
+        }
+
+        let (hi, lo) = if self.top() >= other.top() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        // when the smaller value lies entirely below the last place of the result, only its sign
+        // affects the rounding, so it is replaced with a single bit to keep the sum small
+        let floor = hi.exp.min(hi.top() - prec as i64 - 3);
+        let (lo_mantissa, lo_exp) = if lo.top() <= floor {
+            (BigInt::from(lo.mantissa.signum()), floor - 1)
+        } else {
+            (lo.mantissa.clone(), lo.exp)
+        };
+
+        let exp = hi.exp.min(lo_exp);
+        let sum = (hi.mantissa.clone() << (hi.exp - exp) as usize)
+            + (lo_mantissa << (lo_exp - exp) as usize);
+        Self::from_parts(sum, exp, false, prec, rounding)
+    }
+
+    /// Calculates the square root, or returns `None` if `self` is negative.
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.is_negative() {
+            return None;
+        }
+
+        // the root needs two more bits than the result, and the exponent must be even to halve
+        let mut shift = (2 * (self.prec + 2)).saturating_sub(self.mantissa.bits());
+        if (self.exp - shift as i64) % 2 != 0 {
+            shift += 1;
+        }
+
+        let n = self.mantissa.clone() << shift;
+        let root = n.sqrt();
+        let sticky = &root * &root != n;
+        Some(Self::from_parts(
+            root,
+            (self.exp - shift as i64) / 2,
+            sticky,
+            self.prec,
+            self.rounding,
+        ))
+    }
+
+    /// Calculates `pi` to `prec` bits, with Machin's formula
+    /// `pi = 16 * atan(1 / 5) - 4 * atan(1 / 239)`.
+    pub fn pi(prec: usize) -> Self {
+        let w = prec + 16;
+        let pi = atan_inv(5, w) * 16u8 - atan_inv(239, w) * 4u8;
+        Self::from_parts(pi, -(w as i64), false, prec, Rounding::default())
+    }
+
+    /// Calculates `e^self`, by splitting off a multiple of `ln(2)` into the exponent and summing
+    /// the Taylor series of the rest after halving it several times. Returns `None` if the result
+    /// is too large for its exponent to fit in an `i64`, and rounds to zero if it is too small.
+    pub fn exp(&self) -> Option<Self> {
+        // how many times the reduced argument is halved, then the result squared
+        const HALVINGS: usize = 16;
+
+        if self.is_zero() {
+            return Some(Self::one(self.prec).with_rounding(self.rounding));
+        }
+        // from `2^62` on, the exponent of the result is out of range
+        if self.top() > 62 {
+            return self
+                .is_negative()
+                .then(|| Self::zero(self.prec).with_rounding(self.rounding));
+        }
+
+        let top = self.top().max(0) as usize;
+        let w = self.prec + 32 + 2 * HALVINGS + top;
+
+        let x = self.to_fixed(w);
+        let ln2 = ln2_fixed(w);
+        let k = BigRational::new(x.clone(), ln2.clone()).round();
+        let r = x - &k * &ln2;
+
+        let one = BigInt::one() << w;
+        let scale = BigInt::one() << (w + HALVINGS);
+        let (mut sum, mut term) = (one.clone(), one);
+
+        for i in 1u32.. {
+            term = &term * &r / (&scale * BigInt::from(i));
+            if term == 0 {
+                break;
+            }
+            sum += &term;
+        }
+        for _ in 0..HALVINGS {
+            sum = (&sum * &sum) >> w;
+        }
+
+        // `|self| < 2^62`, so `|k| < 2^62 / ln(2)`
+        let k = i64::try_from(&k).unwrap();
+        Some(Self::from_parts(
+            sum,
+            k - w as i64,
+            false,
+            self.prec,
+            self.rounding,
+        ))
+    }
+
+    /// Calculates the natural logarithm, from `ln(m * 2^e) = 2 * atanh((m - 1) / (m + 1)) +
+    /// e * ln(2)`. Returns `None` if `self` is not positive.
+    pub fn ln(&self) -> Option<Self> {
+        if !self.is_positive() {
+            return None;
+        }
+
+        // near one the result is about `self - 1`, so it needs extra bits to stay accurate
+        let near_one = if (0..=1).contains(&self.top()) && self.exp < 0 {
+            let diff = &self.mantissa - (BigInt::one() << (-self.exp) as usize);
+            if diff == 0 {
+                return Some(Self::zero(self.prec).with_rounding(self.rounding));
+            }
+            (-(diff.bits() as i64 + self.exp)).max(0) as usize
+        } else {
+            0
+        };
+
+        let e = self.top() - 1;
+        let w = self.prec + 32 + near_one + 64 - e.unsigned_abs().leading_zeros() as usize;
+
+        let one = BigInt::one() << w;
+        let m = self.mantissa.clone() << (w as i64 + self.exp - e) as usize;
+        let z = ((&m - &one) << w) / (m + &one);
+        let z2 = (&z * &z) >> w;
+
+        let mut sum = BigInt::zero();
+        let mut term = z;
+        for k in 0u32.. {
+            if term == 0 {
+                break;
+            }
+            sum += &term / BigInt::from(2 * k + 1);
+            term = &term * &z2 / &one;
+        }
+
+        let ln = (sum << 1u8) + ln2_fixed(w) * BigInt::from(e);
+        Some(Self::from_parts(
+            ln,
+            -(w as i64),
+            false,
+            self.prec,
+            self.rounding,
+        ))
+    }
+
+    /// Formats the value in scientific notation with `digits` significant digits, rounding
+    /// halfway cases away from zero.
+    pub fn to_scientific(&self, digits: usize, upper: bool) -> String {
+        let digits = digits.max(1);
+        let e = if upper { 'E' } else { 'e' };
+
+        if self.is_zero() {
+            return match digits {
+                1 => format!("0{e}0"),
+                _ => format!("0.{}{e}0", "0".repeat(digits - 1)),
+            };
+        }
+
+        // estimate the decimal exponent from the binary one, and correct it if it is off by one
+        let mut exp10 = log10_pow2(self.top() - 1);
+        let s = loop {
+            let s = self
+                .scale10_round(digits as i64 - 1 - exp10, digits)
+                .to_string();
+            match s.len().cmp(&digits) {
+                Ordering::Greater => exp10 += 1,
+                Ordering::Less => exp10 -= 1,
+                Ordering::Equal => break s,
+            }
+        };
+
+        let sign = if self.is_negative() { "-" } else { "" };
+        if digits == 1 {
+            format!("{sign}{s}{e}{exp10}")
+        } else {
+            format!("{sign}{}.{}{e}{exp10}", &s[..1], &s[1..])
+        }
+    }
+
+    // `|self| * 10^shift` rounded to an integer, with halfway cases away from zero. The product is
+    // first truncated to a few more bits than either the value or `digits` digits need, so the
+    // cost does not depend on the size of the exponents.
+    fn scale10_round(&self, shift: i64, digits: usize) -> BigInt {
+        let w = Self::precision_for_digits(digits).max(self.prec) + 64;
+        let n = shift.unsigned_abs();
+        let (pow, pow_exp) = if n <= EXACT_POW10_LIMIT {
+            (BigInt::from(10).pow(n), 0)
+        } else {
+            let pow = pow10_approx(n, w);
+            (pow.mantissa, pow.exp)
+        };
+
+        let mag = self.mantissa.abs();
+        let x = if shift >= 0 {
+            Self::from_parts(
+                mag * pow,
+                self.exp + pow_exp,
+                false,
+                w,
+                Rounding::TowardZero,
+            )
+        } else {
+            Self::from_ratio(mag, self.exp - pow_exp, &pow, w, Rounding::TowardZero)
+        };
+
+        if x.exp >= 0 {
+            x.mantissa << x.exp as usize
+        } else {
+            let half = BigInt::one() << (-x.exp - 1) as usize;
+            (x.mantissa + half) >> (-x.exp) as usize
+        }
+    }
+
+    // Formats with the precision requested by the formatter, or otherwise with enough digits to
+    // distinguish the value, without trailing zeros.
+    fn fmt_scientific(&self, f: &mut Formatter, upper: bool) -> fmt::Result {
+        match f.precision() {
+            Some(p) => write!(f, "{}", self.to_scientific(p + 1, upper)),
+            None => {
+                let s = self.to_scientific(self.prec * 30103 / 100000 + 2, upper);
+                let (mantissa, exp) = s.split_at(s.find(['e', 'E']).unwrap());
+                let mantissa = match mantissa.contains('.') {
+                    true => mantissa.trim_end_matches('0').trim_end_matches('.'),
+                    false => mantissa,
+                };
+                write!(f, "{mantissa}{exp}")
+            }
+        }
+    }
+
+    /// Parses a decimal in scientific notation, such as `"-1.25e-3"`, rounded to `prec` bits.
+    pub fn parse(s: &str, prec: usize) -> Result<Self, ParseBigFloatError> {
+        let (mantissa, exp) = match s.find(['e', 'E']) {
+            Some(i) => (&s[..i], &s[i + 1..]),
+            None => (s, "0"),
+        };
+
+        let exp = exp.parse::<i64>().map_err(|_| ParseBigFloatError(()))?;
+        let (whole, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+        let unsigned = whole.strip_prefix(['-', '+']).unwrap_or(whole);
+        let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if !digits(unsigned) || !digits(frac) || unsigned.len() + frac.len() == 0 {
+            return Err(ParseBigFloatError(()));
+        }
+
+        let sign = &whole[..whole.len() - unsigned.len()];
+        let num = BigInt::from_str(&format!("{sign}{unsigned}{frac}0")).unwrap() / 10u8;
+
+        let exp = exp.saturating_sub(frac.len() as i64);
+        if num == 0 {
+            return Ok(Self::zero(prec));
+        }
+        // past this the binary exponent of the result is out of range, so it overflows or
+        // rounds to zero
+        if exp.unsigned_abs() > (i64::MAX / 8) as u64 {
+            return if exp < 0 {
+                Ok(Self::zero(prec))
+            } else {
+                Err(ParseBigFloatError(()))
+            };
+        }
+
+        // exact powers are only calculated while they are not much larger than the result, and
+        // otherwise one with a few more bits than needed is used
+        if exp.unsigned_abs() > (prec as u64).max(EXACT_POW10_LIMIT) {
+            let pow = pow10_approx(exp.unsigned_abs(), prec);
+            let num = Self::from_int(num, pow.prec);
+            let x = if exp >= 0 { &num * &pow } else { &num / &pow };
+            return Ok(x.with_precision(prec));
+        }
+
+        let pow = BigInt::from(10).pow(exp.unsigned_abs());
+        Ok(if exp >= 0 {
+            Self::from_parts(num * pow, 0, false, prec, Rounding::default())
+        } else {
+            Self::from_ratio(num, 0, &pow, prec, Rounding::default())
+        })
+    }
+}
+
+impl FromStr for BigFloat {
+    type Err = ParseBigFloatError;
+
+    /// Parses with the default precision, or more if needed to hold all of the given digits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.chars().take_while(|&c| c != 'e' && c != 'E');
+        let digits = digits.filter(char::is_ascii_digit).count();
+        Self::parse(
+            s,
+            Self::precision_for_digits(digits).max(Self::DEFAULT_PRECISION),
+        )
+    }
+}
+
+impl PartialEq for BigFloat {
+    fn eq(&self, other: &Self) -> bool {
+        self.mantissa == other.mantissa && self.exp == other.exp
+    }
+}
+
+impl Eq for BigFloat {}
+
+impl Ord for BigFloat {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (a, b) = (self.mantissa.signum(), other.mantissa.signum());
+        if a != b || a == 0 {
+            return a.cmp(&b);
+        }
+
+        let mag = self.top().cmp(&other.top()).then_with(|| {
+            let exp = self.exp.min(other.exp);
+            let x = self.mantissa.abs() << (self.exp - exp) as usize;
+            let y = other.mantissa.abs() << (other.exp - exp) as usize;
+            x.cmp(&y)
+        });
+
+        if a < 0 {
+            mag.reverse()
+        } else {
+            mag
+        }
+    }
+}
+
+impl PartialOrd for BigFloat {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Display for BigFloat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_scientific(f, false)
+    }
+}
+
+impl LowerExp for BigFloat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_scientific(f, false)
+    }
+}
+
+impl UpperExp for BigFloat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.fmt_scientific(f, true)
+    }
+}
+
+impl Neg for BigFloat {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        self.mantissa.signed = !self.mantissa.signed && self.mantissa != 0;
+        self
+    }
+}
+
+impl Neg for &BigFloat {
+    type Output = BigFloat;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+macro_rules! impl_float_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, |$a:ident, $b:ident, $prec:ident| $body:expr) => {
+        impl $op<&BigFloat> for &BigFloat {
+            type Output = BigFloat;
+
+            fn $op_fn(self, other: &BigFloat) -> Self::Output {
+                let ($a, $b, $prec) = (self, other, self.prec.max(other.prec));
+                $body
+            }
+        }
+
+        impl $op for BigFloat {
+            type Output = Self;
+
+            fn $op_fn(self, other: Self) -> Self::Output {
+                (&self).$op_fn(&other)
+            }
+        }
+
+        impl $op<&BigFloat> for BigFloat {
+            type Output = Self;
+
+            fn $op_fn(self, other: &Self) -> Self::Output {
+                (&self).$op_fn(other)
+            }
+        }
+
+        impl $op<BigFloat> for &BigFloat {
+            type Output = BigFloat;
+
+            fn $op_fn(self, other: BigFloat) -> Self::Output {
+                self.$op_fn(&other)
+            }
+        }
+
+        impl $assign for BigFloat {
+            fn $assign_fn(&mut self, other: Self) {
+                *self = (&*self).$op_fn(&other);
+            }
+        }
+
+        impl $assign<&BigFloat> for BigFloat {
+            fn $assign_fn(&mut self, other: &Self) {
+                *self = (&*self).$op_fn(other);
+            }
+        }
+    };
+}
+
+impl_float_op!(Add, add, AddAssign, add_assign, |a, b, prec| {
+    a.add_rounded(b, prec, a.rounding)
+});
+
+impl_float_op!(Sub, sub, SubAssign, sub_assign, |a, b, prec| {
+    a.add_rounded(&-b, prec, a.rounding)
+});
+
+impl_float_op!(Mul, mul, MulAssign, mul_assign, |a, b, prec| {
+    BigFloat::from_parts(
+        &a.mantissa * &b.mantissa,
+        a.exp + b.exp,
+        false,
+        prec,
+        a.rounding,
+    )
+});
+
+impl_float_op!(Div, div, DivAssign, div_assign, |a, b, prec| {
+    assert!(!b.is_zero(), "attempt to divide by zero");
+    BigFloat::from_ratio(
+        a.mantissa.clone(),
+        a.exp - b.exp,
+        &b.mantissa,
+        prec,
+        a.rounding,
+    )
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PI: &str = "3.14159265358979323846264338327950288419716939937510582097494";
+    const E: &str = "2.71828182845904523536028747135266249775724709369995957496697";
+    const LN_2: &str = "6.93147180559945309417232121458176568075500134360255254120680";
+    const LN_10: &str = "2.30258509299404568401799145468436420760110148862877297603333";
+
+    fn f(x: f64) -> BigFloat {
+        BigFloat::from_f64(x, 53).unwrap()
+    }
+
+    // xorshift, so the operands are reproducible without a dependency on an rng
+    fn floats(n: usize) -> Vec<f64> {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        (0..n)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                // keep the exponents moderate so the results are normal floats
+                let exp = (seed >> 52) % 200;
+                f64::from_bits(seed & 0x800f_ffff_ffff_ffff | (exp + 923) << 52)
+            })
+            .collect()
+    }
+
+    macro_rules! test_against_f64 {
+        ($name:ident: |$a:ident, $b:ident| $float:expr, $prim:expr) => {
+            #[test]
+            fn $name() {
+                let xs = floats(400);
+                for pair in xs.windows(2) {
+                    let ($a, $b) = (f(pair[0]), f(pair[1]));
+                    let float = $float;
+                    let ($a, $b) = (pair[0], pair[1]);
+                    assert_eq!(float, $prim, "{} {}", $a, $b);
+                }
+            }
+        };
+    }
+
+    test_against_f64!(add: |a, b| (a + b).to_f64(), a + b);
+
+    test_against_f64!(sub: |a, b| (a - b).to_f64(), a - b);
+
+    test_against_f64!(mul: |a, b| (a * b).to_f64(), a * b);
+
+    test_against_f64!(div: |a, b| (a / b).to_f64(), a / b);
+
+    test_against_f64!(sqrt: |a, _b| a.abs().sqrt().unwrap().to_f64(), a.abs().sqrt());
+
+    test_against_f64!(cmp: |a, b| a.cmp(&b), a.partial_cmp(&b).unwrap());
+
+    #[test]
+    fn add_far_apart() {
+        let one = f(1.0);
+        let tiny = f(1e-300);
+        assert_eq!((&one + &tiny).to_f64(), 1.0);
+        assert_eq!((&one - &tiny).to_f64(), 1.0);
+
+        let up = one.clone().with_rounding(Rounding::TowardPositive);
+        let down = one.with_rounding(Rounding::TowardNegative);
+        assert_eq!((&up + &tiny).to_f64(), 1.0 + f64::EPSILON);
+        assert_eq!((&down - &tiny).to_f64(), 1.0 - f64::EPSILON / 2.0);
+        assert_eq!((&down + &tiny).to_f64(), 1.0);
+    }
+
+    #[test]
+    fn rounding_modes() {
+        // 0b1011 and 0b1001 sit between the 3 bit values 0b1010 and 0b1100, or 0b1000 and 0b1010
+        for (x, even, away, zero, pos, neg) in [
+            (0b1011, 12, 12, 10, 12, 10),
+            (0b1001, 8, 10, 8, 10, 8),
+            (-0b1011, -12, -12, -10, -10, -12),
+            (0b10111, 24, 24, 20, 24, 20),
+            (0b1010, 10, 10, 10, 10, 10),
+        ] {
+            let x = BigFloat::from_int(x, 8);
+            for (mode, e) in [
+                (Rounding::TiesToEven, even),
+                (Rounding::TiesToAway, away),
+                (Rounding::TowardZero, zero),
+                (Rounding::TowardPositive, pos),
+                (Rounding::TowardNegative, neg),
+            ] {
+                let r = x.clone().with_rounding(mode).with_precision(3);
+                assert_eq!(r, BigFloat::from_int(e, 3), "{x} {mode:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn canonical() {
+        let x = BigFloat::from_int(96, 10);
+        assert_eq!(x.mantissa(), &BigInt::from(3));
+        assert_eq!(x.exponent(), 5);
+        assert_eq!(BigFloat::zero(10).exponent(), 0);
+        assert_eq!(f(0.5) + f(0.5), BigFloat::one(53));
+        assert!((f(0.1) - f(0.1)).is_zero());
+    }
+
+    #[test]
+    #[should_panic = "attempt to divide by zero"]
+    fn div_zero() {
+        let _ = f(1.0) / BigFloat::zero(53);
+    }
+
+    #[test]
+    fn pi() {
+        let prec = BigFloat::precision_for_digits(60);
+        assert_eq!(format!("{:.59}", BigFloat::pi(prec)), format!("{PI}e0"));
+        assert_eq!(BigFloat::pi(53).to_f64(), std::f64::consts::PI);
+    }
+
+    #[test]
+    fn exp() {
+        let prec = BigFloat::precision_for_digits(60);
+        assert_eq!(
+            format!("{:.59}", BigFloat::one(prec).exp().unwrap()),
+            format!("{E}e0")
+        );
+        assert_eq!(f(0.0).exp().unwrap(), BigFloat::one(53));
+
+        for x in [1e-10, 0.5, -0.5, 3.25, -20.0, 700.0] {
+            let e = f(x).exp().unwrap().to_f64();
+            assert!((e - x.exp()).abs() <= x.exp() * f64::EPSILON, "{x}");
+        }
+    }
+
+    #[test]
+    fn exp_range() {
+        let x = f(1e18).exp().unwrap();
+        assert_eq!(x.top(), 1_442_695_040_888_963_408);
+
+        assert_eq!(f(1e19).exp(), None);
+        assert!(f(-1e19).exp().unwrap().is_zero());
+        assert_eq!(BigFloat::from_int(BigInt::one() << 1000u16, 53).exp(), None);
+    }
+
+    #[test]
+    fn ln() {
+        let prec = BigFloat::precision_for_digits(60);
+        assert_eq!(
+            format!("{:.59}", BigFloat::from_int(2, prec).ln().unwrap()),
+            format!("{LN_2}e-1")
+        );
+        assert_eq!(
+            format!("{:.59}", BigFloat::from_int(10, prec).ln().unwrap()),
+            format!("{LN_10}e0")
+        );
+        assert!(BigFloat::one(53).ln().unwrap().is_zero());
+
+        for x in [1e-300, 0.001, 0.75, 1.0 - 1e-12, 1.0 + 1e-12, 1.5, 1e300] {
+            let l = f(x).ln().unwrap().to_f64();
+            assert!((l - x.ln()).abs() <= x.ln().abs() * f64::EPSILON, "{x}");
+        }
+    }
+
+    #[test]
+    fn exp_ln_inverse() {
+        let prec = 200;
+        for s in ["0.001", "1.5", "-7.25", "123.456"] {
+            let x = BigFloat::parse(s, prec).unwrap();
+            let y = x.exp().unwrap().ln().unwrap();
+            let err = &y - &x;
+            assert!(err.is_zero() || err.top() < -190, "{s} {y}");
+        }
+    }
+
+    #[test]
+    fn out_of_domain() {
+        assert_eq!(BigFloat::zero(53).ln(), None);
+        assert_eq!(f(-2.0).ln(), None);
+        assert_eq!(f(-2.0).sqrt(), None);
+        assert!(BigFloat::zero(53).sqrt().unwrap().is_zero());
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(BigFloat::parse("1.5", 53).unwrap(), f(1.5));
+        assert_eq!(BigFloat::parse("-1.25e-3", 53).unwrap(), f(-1.25e-3));
+        assert_eq!(BigFloat::parse("12E+2", 53).unwrap(), f(1200.0));
+        assert_eq!(BigFloat::parse(".1", 53).unwrap(), f(0.1));
+        assert_eq!(BigFloat::parse("2.", 53).unwrap(), f(2.0));
+        assert_eq!(BigFloat::parse("1e-320", 53).unwrap().to_f64(), 1e-320);
+
+        for s in ["", "e5", "1e", "1.2.3", "--1", "1e5.5", "0x10", "1 "] {
+            assert!(BigFloat::parse(s, 53).is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn parse_large_exponent() {
+        let ten = BigInt::from(10);
+        assert_eq!(
+            BigFloat::parse("3e20000", 53).unwrap(),
+            BigFloat::from_int(ten.pow(20_000) * 3u8, 53)
+        );
+        assert_eq!(
+            BigFloat::parse("-7.5e-20000", 53).unwrap(),
+            BigFloat::from_ratio(
+                BigInt::from(-75),
+                0,
+                &ten.pow(20_001),
+                53,
+                Rounding::default()
+            )
+        );
+
+        assert_eq!(
+            BigFloat::parse("1e1000000000", 53).unwrap().top(),
+            3_321_928_095
+        );
+        assert_eq!(
+            BigFloat::parse("1e-1000000000", 53).unwrap().top(),
+            -3_321_928_094
+        );
+
+        assert!(BigFloat::parse("1e4000000000000000000", 53).is_err());
+        assert!(BigFloat::parse("1e-4000000000000000000", 53)
+            .unwrap()
+            .is_zero());
+        assert!(BigFloat::parse("0e4000000000000000000", 53)
+            .unwrap()
+            .is_zero());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(f(1.5).to_string(), "1.5e0");
+        assert_eq!(f(-1234.5).to_string(), "-1.2345e3");
+        assert_eq!(format!("{:E}", f(0.001)), "1E-3");
+        assert_eq!(format!("{:.3e}", f(2.0 / 3.0)), "6.667e-1");
+        assert_eq!(format!("{:.0e}", f(95.0)), "1e2");
+        assert_eq!(format!("{:.2}", BigFloat::zero(53)), "0.00e0");
+        assert_eq!(f(0.1).to_string(), "1.0000000000000001e-1");
+    }
+
+    #[test]
+    fn display_large_exponent() {
+        let x = BigFloat::parse("1e100000", 53).unwrap();
+        assert_eq!(x.to_string(), "1e100000");
+        assert_eq!(format!("{x:.3e}"), "1.000e100000");
+
+        let x = BigFloat::parse("-2.5e-100000", 53).unwrap();
+        assert_eq!(format!("{x:.3e}"), "-2.500e-100000");
+
+        let x = f(1e18).exp().unwrap();
+        assert_eq!(format!("{x:.9e}"), "4.478462262e434294481903251827");
+    }
+
+    #[test]
+    fn round_trip() {
+        for x in floats(200) {
+            let s = f(x).to_string();
+            assert_eq!(
+                s.parse::<BigFloat>().unwrap().with_precision(53),
+                f(x),
+                "{s}"
+            );
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use std::fmt::{self, Binary, Display, Formatter, LowerHex, Octal, UpperHex};
 
-use crate::BigInt;
+use crate::arithmetic::div::div_rem_limbs;
+use crate::convert::{radix_chunk, RADIX_CONVERSION_THRESHOLD};
+use crate::{Base, BigInt};
 
 impl BigInt {
     /// Converts a `BigInt` into a string with the given base. Panics if `radix` is not in `2..=36`.
@@ -11,27 +13,98 @@ impl BigInt {
             return "0".to_string();
         }
 
-        let radix = BigInt::from(radix);
-        let mut val = self.clone();
-        let mut s = String::new();
-        let mut rem;
+        let mut s = Vec::new();
+        if self.is_negative() {
+            s.push(b'-');
+        }
 
-        while val.abs() > 0 {
-            (val, rem) = val.div_rem(radix.clone());
-            let r = u8::try_from(rem).unwrap();
+        if radix.is_power_of_two() {
+            write_radix_pow2(self, radix, &mut s);
+        } else {
+            let (chunk, _) = radix_chunk(radix);
+            let mut pows = vec![BigInt::from(chunk)];
+            while pows[pows.len() - 1].data.len() * 2 <= self.data.len() {
+                let pow = &pows[pows.len() - 1];
+                pows.push(pow * pow);
+            }
+            write_radix(self.abs(), radix, &pows, 0, &mut s);
+        }
 
-            s.push(match r {
-                0..=9 => r + b'0',
-                10..=26 => r + b'a' - 10,
-                _ => unreachable!(),
-            } as char);
+        String::from_utf8(s).unwrap()
+    }
+}
+
+// Converts a digit into its lowercase ascii character.
+fn digit_char(d: u8) -> u8 {
+    match d {
+        0..=9 => d + b'0',
+        10..=35 => d - 10 + b'a',
+        _ => unreachable!(),
+    }
+}
+
+// Writes the digits of a power of two radix, where each digit is a fixed number of bits and can
+// be read directly from the limbs.
+fn write_radix_pow2(x: &BigInt, radix: u8, out: &mut Vec<u8>) {
+    const BITS: usize = Base::BITS as usize;
+
+    let width = radix.trailing_zeros() as usize;
+    let mask = (1 << width) - 1;
+    let limb = |i: usize| x.data.get(i).copied().unwrap_or(0);
+
+    for i in (0..(x.bits() + width - 1) / width).rev() {
+        let (l, s) = (i * width / BITS, i * width % BITS);
+        let mut d = limb(l) >> s;
+        if s + width > BITS {
+            d |= limb(l + 1) << (BITS - s);
         }
+        out.push(digit_char((d & mask) as u8));
+    }
+}
 
-        if self.is_negative() {
-            s.push('-');
+// Writes the digits of the non-negative `x`, padded with leading zeros to at least `pad` digits.
+// Large values are split around `pows[i] = chunk^(2^i)` into two halves of about the same size,
+// which are written separately, so that the cost is dominated by a few large divisions rather
+// than one limb division for every chunk of digits.
+fn write_radix(x: BigInt, radix: u8, pows: &[BigInt], pad: usize, out: &mut Vec<u8>) {
+    let (chunk, digits) = radix_chunk(radix);
+
+    if x.data.len() < RADIX_CONVERSION_THRESHOLD {
+        let start = out.len();
+        let mut x = x;
+
+        while x != 0 {
+            let mut rem = x.div_rem_limb(chunk);
+            for _ in 0..digits {
+                out.push(digit_char((rem % radix as Base) as u8));
+                rem /= radix as Base;
+            }
         }
-        s.chars().rev().collect()
+
+        // the digits are written least significant first, so leading zeros are at the end
+        while out.len() > start && out.ends_with(&[b'0']) {
+            out.pop();
+        }
+        out.resize(out.len().max(start + pad), b'0');
+        out[start..].reverse();
+        return;
     }
+
+    let i = pows
+        .iter()
+        .rposition(|e| e.data.len() * 2 <= x.data.len() + 1)
+        .unwrap();
+    let (quot, rem) = div_rem_limbs(&x.data, &pows[i].data);
+    let low = digits << i;
+
+    write_radix(
+        BigInt::from_limbs(quot),
+        radix,
+        pows,
+        pad.saturating_sub(low),
+        out,
+    );
+    write_radix(BigInt::from_limbs(rem), radix, pows, low, out);
 }
 
 impl Display for BigInt {
@@ -103,4 +176,61 @@ mod tests {
         BigInt::from(0xffffffff_ffffffff_ffffffff_ffffffffu128),
         "340282366920938463463374607431768211455", "1".repeat(128), format!("3{}", "7".repeat(42)), "f".repeat(32)
     );
+
+    mod radix {
+        use super::*;
+
+        // xorshift, so the operands are reproducible without a dependency on an rng
+        fn limbs(len: usize, seed: &mut u64) -> Vec<Base> {
+            (0..len)
+                .map(|_| {
+                    *seed ^= *seed << 13;
+                    *seed ^= *seed >> 7;
+                    *seed ^= *seed << 17;
+                    *seed as Base
+                })
+                .collect()
+        }
+
+        // one digit at a time, as a reference for the recursive conversion
+        fn naive(x: &BigInt, radix: u8) -> String {
+            let mut x = x.abs();
+            let mut s = String::new();
+            while x != 0 {
+                let d = x.div_rem_limb(radix as Base) as u8;
+                s.insert(0, digit_char(d) as char);
+            }
+            s
+        }
+
+        #[test]
+        fn against_naive() {
+            let mut seed = 0x2545_f491_4f6c_dd1d;
+            for len in [1, 2, 31, 32, 33, 100, 257] {
+                let x = BigInt::from_limbs(limbs(len, &mut seed));
+                for radix in [2, 3, 7, 8, 10, 16, 32, 36] {
+                    assert_eq!(x.to_string_radix(radix), naive(&x, radix), "{len} {radix}");
+                }
+            }
+        }
+
+        #[test]
+        fn inner_zeros() {
+            // runs of zero digits in the middle must survive the split
+            let x = (BigInt::from(7) * BigInt::from(10).pow(1000u16) + 3u8) << 1u8;
+            let s = x.to_string();
+            assert_eq!(s.len(), 1002);
+            assert_eq!(&s[..3], "140");
+            assert_eq!(&s[s.len() - 2..], "06");
+            assert_eq!(s.matches('0').count(), 999);
+        }
+
+        #[test]
+        fn negative() {
+            let x = BigInt::from(-1) - (BigInt::one() << 4000u16);
+            let s = x.to_string_radix(10);
+            assert!(s.starts_with("-1"));
+            assert_eq!(s[1..], naive(&x, 10));
+        }
+    }
 }
@@ -2,42 +2,42 @@
 #![feature(let_chains)]
 #![feature(step_trait)]
 
-// #[cfg(num)]
-use num_bigint;
-
-// #[cfg(num)]
-fn to_num_bigint(x: &BigInt) -> num_bigint::BigInt {
-    num_bigint::BigInt::from_bytes_le(
-        if x.signed {
-            num_bigint::Sign::Minus
-        } else {
-            num_bigint::Sign::Plus
-        },
-        &x.to_le_bytes(),
-    )
-}
-
 pub mod arithmetic;
 pub mod cmp;
+pub mod constant_time;
 pub mod convert;
+pub mod der;
+pub mod factor;
+pub mod float;
 pub mod fmt;
+mod limbs;
 pub mod logical;
 pub mod misc;
+#[cfg(feature = "num-traits")]
+mod num;
+pub mod number_theory;
+pub mod rand;
+pub mod rational;
+#[cfg(feature = "serde")]
+mod serde;
+pub mod uint;
+
+use limbs::Limbs;
 
 #[cfg(target_pointer_width = "64")]
 type Base = u64;
-#[cfg(all(test, target_pointer_width = "64"))]
+#[cfg(target_pointer_width = "64")]
 type BaseExt = u128;
 
 #[cfg(not(target_pointer_width = "64"))]
 type Base = u32;
-#[cfg(all(test, not(target_pointer_width = "64")))]
+#[cfg(not(target_pointer_width = "64"))]
 type BaseExt = u64;
 
 #[derive(Clone, Debug, Eq)]
 pub struct BigInt {
     signed: bool,
-    data: Vec<Base>,
+    data: Limbs,
 }
 
 impl BigInt {
@@ -45,7 +45,7 @@ impl BigInt {
     pub fn zero() -> Self {
         Self {
             signed: false,
-            data: vec![0],
+            data: Limbs::from([0]),
         }
     }
 
@@ -53,7 +53,27 @@ impl BigInt {
     pub fn one() -> Self {
         Self {
             signed: false,
-            data: vec![1],
+            data: Limbs::from([1]),
+        }
+    }
+
+    // Creates a positive `BigInt` from little-endian limbs.
+    pub(crate) fn from_limbs(data: impl Into<Limbs>) -> Self {
+        let mut i = Self {
+            signed: false,
+            data: data.into(),
+        };
+        if i.data.is_empty() {
+            i.data.push(0);
+        }
+        i.trim();
+        i
+    }
+
+    // Removes any leading zero limbs, leaving at least one.
+    pub(crate) fn trim(&mut self) {
+        while self.data.ends_with(&[0]) && self.data.len() > 1 {
+            self.data.pop();
         }
     }
 
@@ -79,7 +99,7 @@ impl BigInt {
 
     /// Returns `1` is `is_positive()`, `-1` if `is_negative()`, or `0` otherwise.
     pub fn signum(&self) -> i8 {
-        if self.data == &[0] {
+        if *self.data == [0] {
             0
         } else {
             if self.signed {
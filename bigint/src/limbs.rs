@@ -0,0 +1,232 @@
+//! Storage for the limbs of a `BigInt`, which keeps small values inline and only allocates once
+//! they outgrow it.
+
+use std::fmt::{self, Debug, Formatter};
+use std::ops::{Deref, DerefMut};
+
+use crate::Base;
+
+/// The number of limbs stored without allocating.
+pub(crate) const INLINE: usize = 2;
+
+/// A growable list of limbs, dereferencing to a slice like a `Vec`.
+#[derive(Clone)]
+pub(crate) enum Limbs {
+    Inline(u8, [Base; INLINE]),
+    Heap(Vec<Base>),
+}
+
+impl Limbs {
+    /// Creates an empty list.
+    pub(crate) fn new() -> Self {
+        Limbs::Inline(0, [0; INLINE])
+    }
+
+    // Moves the limbs to the heap, leaving room for at least `capacity` of them.
+    fn spill(&mut self, capacity: usize) -> &mut Vec<Base> {
+        if let Limbs::Inline(len, data) = self {
+            let mut vec = Vec::with_capacity(capacity.max(2 * INLINE));
+            vec.extend_from_slice(&data[..*len as usize]);
+            *self = Limbs::Heap(vec);
+        }
+
+        match self {
+            Limbs::Heap(vec) => vec,
+            Limbs::Inline(..) => unreachable!(),
+        }
+    }
+
+    /// Appends a limb to the end.
+    pub(crate) fn push(&mut self, x: Base) {
+        match self {
+            Limbs::Inline(len, data) if (*len as usize) < INLINE => {
+                data[*len as usize] = x;
+                *len += 1;
+            }
+            _ => {
+                let len = self.len();
+                self.spill(len + 1).push(x);
+            }
+        }
+    }
+
+    /// Removes the last limb and returns it, or `None` if there are none.
+    pub(crate) fn pop(&mut self) -> Option<Base> {
+        match self {
+            Limbs::Inline(0, _) => None,
+            Limbs::Inline(len, data) => {
+                *len -= 1;
+                Some(data[*len as usize])
+            }
+            Limbs::Heap(vec) => vec.pop(),
+        }
+    }
+
+    /// Changes the number of limbs to `new_len`, filling any new ones with `value`.
+    pub(crate) fn resize(&mut self, new_len: usize, value: Base) {
+        match self {
+            Limbs::Inline(len, data) if new_len <= INLINE => {
+                if new_len > *len as usize {
+                    data[*len as usize..new_len].fill(value);
+                }
+                *len = new_len as u8;
+            }
+            _ => self.spill(new_len).resize(new_len, value),
+        }
+    }
+
+    /// Keeps only the first `new_len` limbs.
+    pub(crate) fn truncate(&mut self, new_len: usize) {
+        match self {
+            Limbs::Inline(len, _) => *len = (*len as usize).min(new_len) as u8,
+            Limbs::Heap(vec) => vec.truncate(new_len),
+        }
+    }
+
+    /// Inserts `n` zero limbs at the start.
+    pub(crate) fn prepend_zeros(&mut self, n: usize) {
+        let len = self.len();
+        self.resize(len + n, 0);
+        self.copy_within(..len, n);
+        self[..n].fill(0);
+    }
+
+    /// Removes the first `n` limbs.
+    pub(crate) fn remove_front(&mut self, n: usize) {
+        let len = self.len();
+        self.copy_within(n.., 0);
+        self.truncate(len - n);
+    }
+}
+
+impl Deref for Limbs {
+    type Target = [Base];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Limbs::Inline(len, data) => &data[..*len as usize],
+            Limbs::Heap(vec) => vec,
+        }
+    }
+}
+
+impl DerefMut for Limbs {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Limbs::Inline(len, data) => &mut data[..*len as usize],
+            Limbs::Heap(vec) => vec,
+        }
+    }
+}
+
+impl PartialEq for Limbs {
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl Eq for Limbs {}
+
+impl Debug for Limbs {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl From<Vec<Base>> for Limbs {
+    fn from(vec: Vec<Base>) -> Self {
+        if vec.len() <= INLINE {
+            Limbs::from(&vec[..])
+        } else {
+            Limbs::Heap(vec)
+        }
+    }
+}
+
+impl From<&[Base]> for Limbs {
+    fn from(slice: &[Base]) -> Self {
+        if slice.len() <= INLINE {
+            let mut data = [0; INLINE];
+            data[..slice.len()].copy_from_slice(slice);
+            Limbs::Inline(slice.len() as u8, data)
+        } else {
+            Limbs::Heap(slice.to_vec())
+        }
+    }
+}
+
+impl<const N: usize> From<[Base; N]> for Limbs {
+    fn from(array: [Base; N]) -> Self {
+        Limbs::from(&array[..])
+    }
+}
+
+impl From<Limbs> for Vec<Base> {
+    fn from(limbs: Limbs) -> Self {
+        match limbs {
+            Limbs::Inline(len, data) => data[..len as usize].to_vec(),
+            Limbs::Heap(vec) => vec,
+        }
+    }
+}
+
+impl FromIterator<Base> for Limbs {
+    fn from_iter<I: IntoIterator<Item = Base>>(iter: I) -> Self {
+        let mut limbs = Limbs::new();
+        for x in iter {
+            limbs.push(x);
+        }
+        limbs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_pop() {
+        let mut limbs = Limbs::new();
+        for i in 0..10 {
+            limbs.push(i);
+            assert_eq!(*limbs, (0..=i).collect::<Vec<_>>());
+            assert_eq!(matches!(limbs, Limbs::Inline(..)), i < INLINE as Base);
+        }
+        for i in (0..10).rev() {
+            assert_eq!(limbs.pop(), Some(i));
+        }
+        assert_eq!(limbs.pop(), None);
+    }
+
+    #[test]
+    fn resize() {
+        let mut limbs = Limbs::from([1]);
+        limbs.resize(2, 7);
+        assert_eq!(*limbs, [1, 7]);
+        limbs.resize(5, 0);
+        assert_eq!(*limbs, [1, 7, 0, 0, 0]);
+        limbs.resize(1, 0);
+        assert_eq!(*limbs, [1]);
+        limbs.truncate(0);
+        assert!(limbs.is_empty());
+    }
+
+    #[test]
+    fn shift() {
+        let mut limbs = Limbs::from([1, 2]);
+        limbs.prepend_zeros(2);
+        assert_eq!(*limbs, [0, 0, 1, 2]);
+        limbs.remove_front(3);
+        assert_eq!(*limbs, [2]);
+    }
+
+    #[test]
+    fn eq() {
+        // the same limbs are equal wherever they are stored
+        let mut heap = Limbs::from(vec![1, 2, 3]);
+        heap.pop();
+        assert!(matches!(heap, Limbs::Heap(_)));
+        assert_eq!(heap, Limbs::from([1, 2]));
+        assert_eq!(Vec::from(heap), [1, 2]);
+    }
+}
@@ -1,6 +1,146 @@
+//! Bitwise operations and shifts.
+//!
+//! Negative values behave as if they were stored in two's complement with an infinite number of
+//! leading ones, the same as the primitive signed integers, so `-5 & 3 == 3` and `-1 >> 1 == -1`.
+
 pub mod and;
 pub mod not;
 pub mod or;
 pub mod shl;
 pub mod shr;
 pub mod xor;
+
+use crate::{Base, BigInt, Limbs};
+
+// Negates two's complement limbs in place, by inverting them and adding one.
+fn negate_limbs(limbs: &mut [Base]) {
+    let mut carry = true;
+    for e in limbs.iter_mut() {
+        (*e, carry) = (!*e).overflowing_add(carry as Base);
+    }
+}
+
+// The limbs of `x` in two's complement, sign-extended to `len` limbs, which must be more than
+// `x` has so the sign is kept.
+fn to_twos_complement(x: &BigInt, len: usize) -> Limbs {
+    let mut limbs = x.data.clone();
+    limbs.resize(len, 0);
+    if x.signed {
+        negate_limbs(&mut limbs);
+    }
+    limbs
+}
+
+// Creates a `BigInt` from two's complement limbs, which are negative when the top bit is set.
+fn from_twos_complement(mut limbs: Limbs) -> BigInt {
+    let signed = limbs.last().map_or(false, |e| e >> (Base::BITS - 1) == 1);
+    if signed {
+        negate_limbs(&mut limbs);
+    }
+
+    let mut x = BigInt::from_limbs(limbs);
+    x.signed = signed;
+    x
+}
+
+// Applies `op` to each pair of limbs of `a` and `b` in two's complement.
+fn bitwise(a: &BigInt, b: &BigInt, op: impl Fn(Base, Base) -> Base) -> BigInt {
+    let len = a.data.len().max(b.data.len()) + 1;
+    let (a, b) = (to_twos_complement(a, len), to_twos_complement(b, len));
+    from_twos_complement(a.iter().zip(b.iter()).map(|(&a, &b)| op(a, b)).collect())
+}
+
+impl BigInt {
+    /// Returns the bit at index `i` in two's complement, which is always set above the highest
+    /// bit of a negative number.
+    pub fn bit(&self, i: usize) -> bool {
+        let magnitude = |i: usize| {
+            let limb = i / Base::BITS as usize;
+            limb < self.data.len() && self.data[limb] >> (i % Base::BITS as usize) & 1 == 1
+        };
+
+        if !self.signed {
+            return magnitude(i);
+        }
+
+        // `-x` is `!(x - 1)`, and subtracting one only flips the bits up to the lowest one
+        let tz = self.trailing_zeros();
+        match i.cmp(&tz) {
+            std::cmp::Ordering::Less => false,
+            std::cmp::Ordering::Equal => true,
+            std::cmp::Ordering::Greater => !magnitude(i),
+        }
+    }
+
+    /// Sets the bit at index `i` in two's complement to `value`.
+    pub fn set_bit(&mut self, i: usize, value: bool) {
+        if self.bit(i) != value {
+            self.toggle_bit(i);
+        }
+    }
+
+    /// Flips the bit at index `i` in two's complement.
+    pub fn toggle_bit(&mut self, i: usize) {
+        *self = bitwise(self, &(BigInt::one() << i), |a, b| a ^ b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) const VALUES: [i128; 12] = [
+        0,
+        1,
+        -1,
+        5,
+        -5,
+        -8,
+        0x1234_5678_9abc,
+        -0x1234_5678_9abc,
+        i64::MIN as i128,
+        u64::MAX as i128,
+        -(u64::MAX as i128) - 1,
+        i128::MIN / 3,
+    ];
+
+    #[test]
+    fn bit() {
+        for x in VALUES {
+            for i in 0..128 {
+                assert_eq!(BigInt::from(x).bit(i), x >> i & 1 == 1, "{x} {i}");
+            }
+            assert_eq!(BigInt::from(x).bit(1000), x < 0);
+        }
+    }
+
+    #[test]
+    fn set_bit() {
+        for x in VALUES {
+            for i in [0, 3, 63, 64, 100] {
+                let mut a = BigInt::from(x);
+                a.set_bit(i, true);
+                assert_eq!(a, x | 1 << i, "{x} {i}");
+
+                let mut a = BigInt::from(x);
+                a.set_bit(i, false);
+                assert_eq!(a, x & !(1 << i), "{x} {i}");
+
+                let mut a = BigInt::from(x);
+                a.toggle_bit(i);
+                assert_eq!(a, x ^ 1 << i, "{x} {i}");
+            }
+        }
+    }
+
+    #[test]
+    fn set_bit_above() {
+        let mut a = BigInt::from(-1);
+        a.set_bit(200, false);
+        assert_eq!(a, BigInt::from(-1) - (BigInt::one() << 200u8));
+
+        let mut a = BigInt::zero();
+        a.toggle_bit(130);
+        assert_eq!(a, BigInt::one() << 130u8);
+    }
+}
@@ -1,6 +1,6 @@
-use std::mem;
 use std::ops::{BitAnd, BitAndAssign};
 
+use super::bitwise;
 use crate::BigInt;
 
 impl BitAnd for BigInt {
@@ -13,16 +13,8 @@ impl BitAnd for BigInt {
 }
 
 impl BitAndAssign for BigInt {
-    fn bitand_assign(&mut self, mut other: Self) {
-        if self.data.len() > other.data.len() {
-            mem::swap(self, &mut other);
-        }
-
-        for i in 0..self.data.len() {
-            if i < other.data.len() {
-                self.data[i] &= other.data[i];
-            }
-        }
+    fn bitand_assign(&mut self, other: Self) {
+        *self = bitwise(self, &other, |a, b| a & b);
     }
 }
 
@@ -51,6 +43,7 @@ impl_primitive_and!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logical::tests::VALUES;
     use crate::{Base, BaseExt};
 
     macro_rules! test_and {
@@ -67,4 +60,13 @@ mod tests {
     test_and!(one: BigInt::from(Base::MAX), BigInt::one(), 1);
 
     test_and!(big_zero: BigInt::from(BaseExt::MAX), BigInt::zero(), 0);
+
+    #[test]
+    fn against_i128() {
+        for a in VALUES {
+            for b in VALUES {
+                assert_eq!(BigInt::from(a) & BigInt::from(b), a & b, "{a} {b}");
+            }
+        }
+    }
 }
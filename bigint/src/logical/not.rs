@@ -1,38 +1,28 @@
 use std::ops::Not;
 
-use crate::{Base, BigInt};
+use crate::BigInt;
 
 impl Not for BigInt {
     type Output = Self;
 
+    /// In two's complement `!x` is `-x - 1`.
     fn not(mut self) -> Self::Output {
-        let mut i = 0;
-        while i < self.data.len() - 1 {
-            self.data[i] = !self.data[i];
-            i += 1;
+        if self.signed {
+            self.signed = false;
+            self - 1u8
+        } else {
+            self += 1u8;
+            self.signed = true;
+            self
         }
-
-        let shift =
-            (Base::BITS - self.data[i].leading_zeros()) as i8 + if self >= 0 { 1 } else { -1 };
-        let (mask, overflow) = (1 as Base).overflowing_shl(shift as u32);
-        self.data[i] = !self.data[i] & (mask - 1);
-
-        if overflow {
-            self.data.push(1);
-        }
-        while self.data.ends_with(&[0]) && self.data.len() > 1 {
-            self.data.pop();
-        }
-
-        self.signed ^= true;
-        self
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::BaseExt;
+    use crate::logical::tests::VALUES;
+    use crate::{Base, BaseExt};
 
     macro_rules! test_not {
         ($name:ident: $a:expr, $e:expr) => {
@@ -55,19 +45,30 @@ mod tests {
         max: BigInt::from(Base::MAX),
         BigInt {
             signed: true,
-            data: vec![0, 1]
+            data: [0, 1].into()
         }
     );
 
-    test_not!(max_inv: BigInt { signed: true, data: vec![0, 1] }, Base::MAX);
+    test_not!(max_inv: BigInt { signed: true, data: [0, 1].into() }, Base::MAX);
 
     test_not!(
         big: BigInt::from(BaseExt::MAX),
         BigInt {
             signed: true,
-            data: vec![0, 0, 1]
+            data: [0, 0, 1].into()
         }
     );
 
-    test_not!(big_inv: BigInt { signed: true, data: vec![0, 0, 1] }, BaseExt::MAX);
+    test_not!(big_inv: BigInt { signed: true, data: [0, 0, 1].into() }, BaseExt::MAX);
+
+    test_not!(five: BigInt::from(5), -6);
+
+    test_not!(neg_five: BigInt::from(-5), 4);
+
+    #[test]
+    fn against_i128() {
+        for a in VALUES {
+            assert_eq!(!BigInt::from(a), !a, "{a}");
+        }
+    }
 }
@@ -1,6 +1,6 @@
-use std::mem;
 use std::ops::{BitOr, BitOrAssign};
 
+use super::bitwise;
 use crate::BigInt;
 
 impl BitOr for BigInt {
@@ -13,16 +13,8 @@ impl BitOr for BigInt {
 }
 
 impl BitOrAssign for BigInt {
-    fn bitor_assign(&mut self, mut other: Self) {
-        if self.data.len() < other.data.len() {
-            mem::swap(self, &mut other);
-        }
-
-        for i in 0..self.data.len() {
-            if i < other.data.len() {
-                self.data[i] |= other.data[i];
-            }
-        }
+    fn bitor_assign(&mut self, other: Self) {
+        *self = bitwise(self, &other, |a, b| a | b);
     }
 }
 
@@ -51,6 +43,7 @@ impl_primitive_or!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logical::tests::VALUES;
     use crate::Base;
 
     macro_rules! test_or {
@@ -65,4 +58,13 @@ mod tests {
     test_or!(one: BigInt::one(), BigInt::zero(), 1);
 
     test_or!(max: BigInt::from(Base::MAX), BigInt::zero(), Base::MAX);
+
+    #[test]
+    fn against_i128() {
+        for a in VALUES {
+            for b in VALUES {
+                assert_eq!(BigInt::from(a) | BigInt::from(b), a | b, "{a} {b}");
+            }
+        }
+    }
 }
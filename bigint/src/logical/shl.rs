@@ -2,6 +2,29 @@ use std::ops::{Shl, ShlAssign};
 
 use crate::{Base, BigInt};
 
+// Shifting left multiplies by a power of two, so only the magnitude changes.
+fn shl_usize(x: &mut BigInt, len: usize) {
+    const BITS: usize = Base::BITS as usize;
+
+    if *x == 0 {
+        return;
+    }
+
+    let bits = len % BITS;
+    if bits > 0 {
+        let mut carry = 0;
+        for e in x.data.iter_mut() {
+            let next = *e >> (BITS - bits);
+            *e = *e << bits | carry;
+            carry = next;
+        }
+        if carry > 0 {
+            x.data.push(carry);
+        }
+    }
+    x.data.prepend_zeros(len / BITS);
+}
+
 macro_rules! impl_primitive_shl {
     ($($t:ty),*) => {
         $(
@@ -15,29 +38,10 @@ macro_rules! impl_primitive_shl {
             }
 
             impl ShlAssign<$t> for BigInt {
-                fn shl_assign(&mut self, mut len: $t) {
-                    const BITS: $t = Base::BITS as $t;
-
-                    while len > 0 {
-                        let mut v = vec![0; self.data.len()];
-                        let l = len.clamp(0, BITS - 1);
-
-                        for i in 0..(self.data.len()) {
-                            let val = self.data[i] << l;
-                            let carry = self.data[i] >> BITS - l;
-
-                            v[i] += val;
-                            if carry > 0 {
-                                if v.len() == i + 1 {
-                                    v.push(0);
-                                }
-                                v[i + 1] = carry;
-                            }
-                        }
-                        self.data = v;
-
-                        len = len.saturating_sub(BITS - 1);
-                    }
+                /// Panics if `len` is negative.
+                fn shl_assign(&mut self, len: $t) {
+                    let len = usize::try_from(len).expect("attempt to shift left with overflow");
+                    shl_usize(self, len);
                 }
             }
         )*
@@ -49,6 +53,7 @@ impl_primitive_shl!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isi
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logical::tests::VALUES;
     use crate::BaseExt;
 
     macro_rules! test_shl {
@@ -79,7 +84,22 @@ mod tests {
         BaseExt::BITS,
         BigInt {
             signed: false,
-            data: vec![0, 0, 1]
+            data: [0, 0, 1].into()
         }
     );
+
+    test_shl!(negative: BigInt::from(-3), 65, -3i128 << 65);
+
+    test_shl!(zero: BigInt::zero(), 1000, 0);
+
+    #[test]
+    fn against_i128() {
+        for a in VALUES {
+            for b in [0, 1, 7, 63, 64] {
+                if let Some(e) = a.checked_mul(1 << b) && e != i128::MIN {
+                    assert_eq!(BigInt::from(a) << b, e, "{a} {b}");
+                }
+            }
+        }
+    }
 }
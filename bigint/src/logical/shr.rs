@@ -1,6 +1,39 @@
 use std::ops::{Shr, ShrAssign};
 
-use crate::{Base, BigInt};
+use crate::{Base, BigInt, Limbs};
+
+// Shifting right divides by a power of two, rounding towards negative infinity as in two's
+// complement, so a negative value is one further from zero if any set bits are shifted out.
+fn shr_usize(x: &mut BigInt, len: usize) {
+    const BITS: usize = Base::BITS as usize;
+
+    let signed = x.signed;
+    let round = signed && x.trailing_zeros() < len;
+
+    let limbs = len / BITS;
+    if limbs >= x.data.len() {
+        x.data = Limbs::from([0]);
+    } else {
+        x.data.remove_front(limbs);
+
+        let bits = len % BITS;
+        if bits > 0 {
+            let mut carry = 0;
+            for e in x.data.iter_mut().rev() {
+                let next = *e << (BITS - bits);
+                *e = *e >> bits | carry;
+                carry = next;
+            }
+        }
+        x.trim();
+    }
+
+    x.signed = false;
+    if round {
+        *x += 1u8;
+    }
+    x.signed = signed && *x != 0;
+}
 
 macro_rules! impl_primitive_shr {
     ($($t:ty),*) => {
@@ -15,29 +48,15 @@ macro_rules! impl_primitive_shr {
             }
 
             impl ShrAssign<$t> for BigInt {
-                fn shr_assign(&mut self, mut len: $t) {
-                    const BITS: $t = Base::BITS as $t;
-
-                    while len > 0 {
-                        let mut v = vec![0; self.data.len()];
-                        let l = len.clamp(0, BITS - 1);
-
-                        for i in (0..(self.data.len())).rev() {
-                            let val = self.data[i] >> l;
-                            let carry = self.data[i] << BITS - l;
-
-                            v[i] += val;
-                            if i > 0 {
-                                v[i - 1] = carry;
-                            }
-                        }
-                        self.data = v;
-
-                        while self.data.ends_with(&[0]) && self.data.len() > 1 {
-                            self.data.pop();
-                        }
-                        len = len.saturating_sub(BITS - 1);
-                    }
+                /// Panics if `len` is negative.
+                fn shr_assign(&mut self, len: $t) {
+                    // anything too large for a `usize` shifts out every bit
+                    let len = match usize::try_from(len) {
+                        Ok(len) => len,
+                        Err(_) if len > 0 => usize::MAX,
+                        Err(_) => panic!("attempt to shift right with overflow"),
+                    };
+                    shr_usize(self, len);
                 }
             }
         )*
@@ -49,6 +68,7 @@ impl_primitive_shr!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isi
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logical::tests::VALUES;
     use crate::BaseExt;
 
     macro_rules! test_shr {
@@ -66,5 +86,24 @@ mod tests {
 
     test_shr!(overflow: BigInt::from(Base::MAX as BaseExt + 1), Base::BITS, 1);
 
-    test_shr!(big: BigInt { signed: false, data: vec![Base::MAX, Base::MAX, 1] }, BaseExt::BITS, 1);
+    test_shr!(big: BigInt { signed: false, data: [Base::MAX, Base::MAX, 1].into() }, BaseExt::BITS, 1);
+
+    test_shr!(neg_one: BigInt::from(-1), 1, -1);
+
+    test_shr!(negative: BigInt::from(-5), 1, -3);
+
+    test_shr!(negative_exact: BigInt::from(-8), 3, -1);
+
+    test_shr!(negative_all: BigInt::from(-5), 1000, -1);
+
+    test_shr!(huge: BigInt::from(5), u128::MAX, 0);
+
+    #[test]
+    fn against_i128() {
+        for a in VALUES {
+            for b in [0, 1, 7, 63, 64, 65, 127] {
+                assert_eq!(BigInt::from(a) >> b, a >> b, "{a} {b}");
+            }
+        }
+    }
 }
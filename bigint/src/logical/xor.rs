@@ -1,6 +1,6 @@
-use std::mem;
 use std::ops::{BitXor, BitXorAssign};
 
+use super::bitwise;
 use crate::BigInt;
 
 impl BitXor for BigInt {
@@ -13,19 +13,8 @@ impl BitXor for BigInt {
 }
 
 impl BitXorAssign for BigInt {
-    fn bitxor_assign(&mut self, mut other: Self) {
-        if self.data.len() > other.data.len() {
-            mem::swap(self, &mut other);
-        }
-
-        for i in 0..self.data.len() {
-            if i < other.data.len() {
-                self.data[i] ^= other.data[i];
-            }
-        }
-        while self.data.ends_with(&[0]) && self.data.len() > 1 {
-            self.data.pop();
-        }
+    fn bitxor_assign(&mut self, other: Self) {
+        *self = bitwise(self, &other, |a, b| a ^ b);
     }
 }
 
@@ -54,6 +43,7 @@ impl_primitive_xor!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::logical::tests::VALUES;
     use crate::Base;
 
     macro_rules! test_xor {
@@ -80,4 +70,13 @@ mod tests {
     test_xor!(seven: BigInt::from(3u8), BigInt::from(4u8), 7);
 
     test_xor!(eight: BigInt::from(12u8), BigInt::from(4u8), 8);
+
+    #[test]
+    fn against_i128() {
+        for a in VALUES {
+            for b in VALUES {
+                assert_eq!(BigInt::from(a) ^ BigInt::from(b), a ^ b, "{a} {b}");
+            }
+        }
+    }
 }
@@ -0,0 +1,187 @@
+//! Implementations of the `num-traits` traits, so `BigInt` can be used in generic numeric code.
+
+use num_traits::{Num, One, Pow, Signed, ToPrimitive, Zero};
+
+use crate::convert::ParseBigIntError;
+use crate::BigInt;
+
+impl Zero for BigInt {
+    fn zero() -> Self {
+        BigInt::zero()
+    }
+
+    fn is_zero(&self) -> bool {
+        self.signum() == 0
+    }
+}
+
+impl One for BigInt {
+    fn one() -> Self {
+        BigInt::one()
+    }
+
+    fn is_one(&self) -> bool {
+        *self == 1
+    }
+}
+
+impl Num for BigInt {
+    type FromStrRadixErr = ParseBigIntError;
+
+    /// Panics if `radix` is not in `2..=36`.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        BigInt::from_str_radix(s, radix.try_into().unwrap_or(u8::MAX))
+    }
+}
+
+impl Signed for BigInt {
+    fn abs(&self) -> Self {
+        BigInt::abs(self)
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other {
+            BigInt::zero()
+        } else {
+            self - other
+        }
+    }
+
+    fn signum(&self) -> Self {
+        BigInt::from(BigInt::signum(self))
+    }
+
+    fn is_positive(&self) -> bool {
+        BigInt::is_positive(self)
+    }
+
+    fn is_negative(&self) -> bool {
+        BigInt::is_negative(self)
+    }
+}
+
+macro_rules! impl_pow {
+    ($($t:ty),*) => {
+        $(
+            impl Pow<$t> for BigInt {
+                type Output = BigInt;
+
+                fn pow(self, exp: $t) -> Self::Output {
+                    BigInt::pow(&self, exp)
+                }
+            }
+
+            impl Pow<$t> for &BigInt {
+                type Output = BigInt;
+
+                fn pow(self, exp: $t) -> Self::Output {
+                    BigInt::pow(self, exp)
+                }
+            }
+        )*
+    }
+}
+
+impl_pow!(u8, u16, u32, u64, u128, usize, BigInt);
+
+impl ToPrimitive for BigInt {
+    fn to_i64(&self) -> Option<i64> {
+        self.try_into().ok()
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        self.try_into().ok()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.try_into().ok()
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        self.try_into().ok()
+    }
+
+    fn to_f32(&self) -> Option<f32> {
+        Some(BigInt::to_f32(self))
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        Some(BigInt::to_f64(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sums the first `n` terms of `x^i / i!`, truncating each term.
+    fn exp_series<T: Num + Clone + From<u8>>(x: T, n: u8) -> T {
+        let mut term = T::one();
+        let mut acc = T::zero();
+        for i in 1..=n {
+            acc = acc + term.clone();
+            term = term * x.clone() / T::from(i);
+        }
+        acc
+    }
+
+    #[test]
+    fn generic() {
+        let x = BigInt::from(10).pow(30u8);
+        assert_eq!(
+            exp_series(x.clone(), 3),
+            BigInt::one() + &x + &x * &x / BigInt::from(2)
+        );
+        assert_eq!(exp_series(BigInt::from(3), 10), exp_series(3u64, 10));
+    }
+
+    #[test]
+    fn zero_one() {
+        assert!(<BigInt as Zero>::is_zero(&BigInt::from(-0)));
+        assert!(!<BigInt as Zero>::is_zero(&BigInt::from(-1)));
+        assert!(<BigInt as One>::is_one(&BigInt::from(1)));
+        assert!(!<BigInt as One>::is_one(&BigInt::from(-1)));
+    }
+
+    #[test]
+    fn from_str_radix() {
+        assert_eq!(<BigInt as Num>::from_str_radix("-ff", 16).unwrap(), -255);
+        assert!(<BigInt as Num>::from_str_radix("z", 10).is_err());
+    }
+
+    #[test]
+    fn signed() {
+        let (a, b) = (BigInt::from(-7), BigInt::from(3));
+        assert_eq!(Signed::abs(&a), 7);
+        assert_eq!(a.abs_sub(&b), 0);
+        assert_eq!(b.abs_sub(&a), 10);
+        assert_eq!(Signed::signum(&a), -1);
+        assert_eq!(Signed::signum(&BigInt::zero()), 0);
+        assert!(Signed::is_negative(&a));
+    }
+
+    #[test]
+    fn pow() {
+        assert_eq!(Pow::pow(BigInt::from(-3), 5u32), -243);
+        assert_eq!(
+            Pow::pow(&BigInt::from(2), BigInt::from(100)),
+            BigInt::one() << 100u8
+        );
+    }
+
+    #[test]
+    fn to_primitive() {
+        let x = BigInt::from(i64::MIN);
+        assert_eq!(x.to_i64(), Some(i64::MIN));
+        assert_eq!(x.to_i32(), None);
+        assert_eq!(x.to_u64(), None);
+        assert_eq!((x - 1u8).to_i64(), None);
+        assert_eq!(BigInt::from(300).to_u8(), None);
+        assert_eq!(BigInt::from(300).to_u16(), Some(300));
+        assert_eq!(BigInt::from(u128::MAX).to_u128(), Some(u128::MAX));
+        assert_eq!(
+            ToPrimitive::to_f64(&(BigInt::one() << 2000u16)),
+            Some(f64::INFINITY)
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod combinatorics;
+pub mod gcd;
+pub mod prime;
+pub mod residue;
@@ -0,0 +1,252 @@
+use crate::factor::primes_to;
+use crate::BigInt;
+
+// Products of fewer numbers than this are multiplied one at a time.
+const PRODUCT_SPLIT: usize = 16;
+
+// Binomial coefficients `n choose k` are built from prime powers, which needs a sieve up to `n`.
+// That is cheap for `n` up to this, and past it only beats dividing by `k!` when `k` is at least
+// `n / BINOMIAL_SIEVE_RATIO`. Past `BINOMIAL_SIEVE_MAX` the sieve would take too much memory.
+const BINOMIAL_SIEVE_CHEAP: u64 = 1 << 20;
+const BINOMIAL_SIEVE_RATIO: u64 = 256;
+const BINOMIAL_SIEVE_MAX: u64 = 1 << 28;
+
+// Multiplies the numbers by splitting them in half, so the final multiplications are between
+// operands of similar sizes and can use the faster algorithms.
+fn product(xs: &[u64]) -> BigInt {
+    if xs.len() < PRODUCT_SPLIT {
+        xs.iter().fold(BigInt::one(), |acc, &x| acc * x)
+    } else {
+        let (lo, hi) = xs.split_at(xs.len() / 2);
+        product(lo) * product(hi)
+    }
+}
+
+// The product of `lo..hi`, split in the same way as `product`.
+fn range_product(lo: u64, hi: u64) -> BigInt {
+    if hi - lo < PRODUCT_SPLIT as u64 {
+        (lo..hi).fold(BigInt::one(), |acc, x| acc * x)
+    } else {
+        let mid = lo + (hi - lo) / 2;
+        range_product(lo, mid) * range_product(mid, hi)
+    }
+}
+
+// The exponent of the prime `p` in `n!`, from Legendre's formula.
+fn factorial_exponent(n: u64, p: u64) -> u64 {
+    let mut e = 0;
+    let mut n = n;
+    while n > 0 {
+        n /= p;
+        e += n;
+    }
+    e
+}
+
+// `(F_n, F_n+1)` with the fast doubling identities `F_2k = F_k (2 F_k+1 - F_k)` and
+// `F_2k+1 = F_k^2 + F_k+1^2`.
+fn fibonacci_pair(n: u64) -> (BigInt, BigInt) {
+    if n == 0 {
+        return (BigInt::zero(), BigInt::one());
+    }
+
+    let (a, b) = fibonacci_pair(n / 2);
+    let c = &a * &((b.clone() << 1u8) - &a);
+    let d = &a * &a + &b * &b;
+
+    if n % 2 == 0 {
+        (c, d)
+    } else {
+        let e = &c + &d;
+        (d, e)
+    }
+}
+
+impl BigInt {
+    /// Calculates `n!`, the product of `1..=n`.
+    pub fn factorial(n: u64) -> BigInt {
+        range_product(2, n.max(1) + 1)
+    }
+
+    /// Calculates the binomial coefficient `n choose k`, the number of ways to pick `k` items from
+    /// `n`, which is `0` when `k > n`.
+    ///
+    /// Small values of `k` use the multiplicative formula. When `n` is small or not much larger
+    /// than `k` the prime powers dividing the result are found with Kummer's theorem and
+    /// multiplied together, and otherwise `n! / (n - k)!` is divided by `k!`.
+    pub fn binomial(n: u64, k: u64) -> BigInt {
+        if k > n {
+            return BigInt::zero();
+        }
+        let k = k.min(n - k);
+
+        if k < PRODUCT_SPLIT as u64 {
+            // each partial result is itself a binomial coefficient, so the division is exact
+            return (1..=k).fold(BigInt::one(), |acc, i| acc * (n - k + i) / i);
+        }
+        let sieve =
+            n <= BINOMIAL_SIEVE_CHEAP || n <= BINOMIAL_SIEVE_MAX && n / BINOMIAL_SIEVE_RATIO <= k;
+        if !sieve {
+            // `n` is multiplied in separately, since `n + 1` may overflow
+            return range_product(n - k + 1, n) * n / BigInt::factorial(k);
+        }
+
+        let powers = primes_to(n)
+            .into_iter()
+            .filter_map(|p| {
+                let e = factorial_exponent(n, p)
+                    - factorial_exponent(k, p)
+                    - factorial_exponent(n - k, p);
+                (e > 0).then(|| p.pow(e as u32))
+            })
+            .collect::<Vec<_>>();
+        product(&powers)
+    }
+
+    /// Calculates the multinomial coefficient `(k_1 + k_2 + ...)! / (k_1! k_2! ...)`, the number
+    /// of ways to split a set into groups of the given sizes. Panics if the total overflows a
+    /// `u64`.
+    pub fn multinomial(ks: &[u64]) -> BigInt {
+        let mut n = 0u64;
+        let mut acc = BigInt::one();
+
+        for &k in ks {
+            n = n.checked_add(k).expect("attempt to add with overflow");
+            acc *= BigInt::binomial(n, k);
+        }
+        acc
+    }
+
+    /// Calculates the `n`th Fibonacci number, with `F_0 = 0` and `F_1 = 1`, by fast doubling.
+    pub fn fibonacci(n: u64) -> BigInt {
+        fibonacci_pair(n).0
+    }
+
+    /// Calculates the `n`th Lucas number, with `L_0 = 2` and `L_1 = 1`, from the Fibonacci
+    /// numbers as `L_n = 2 F_n+1 - F_n`.
+    pub fn lucas(n: u64) -> BigInt {
+        let (f, f1) = fibonacci_pair(n);
+        (f1 << 1u8) - f
+    }
+
+    /// Calculates `n#`, the product of the primes up to and including `n`.
+    pub fn primorial(n: u64) -> BigInt {
+        product(&primes_to(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factorial() {
+        let mut e = BigInt::one();
+        for n in 0..200u64 {
+            if n > 0 {
+                e *= n;
+            }
+            assert_eq!(BigInt::factorial(n), e, "{n}");
+        }
+    }
+
+    #[test]
+    fn factorial_large() {
+        let f = BigInt::factorial(10_000);
+        assert_eq!(f.to_string().len(), 35_660);
+        assert_eq!(f.trailing_zeros(), factorial_exponent(10_000, 2) as usize);
+    }
+
+    #[test]
+    fn binomial() {
+        // Pascal's triangle
+        let mut row = vec![BigInt::one()];
+        for n in 1..=120u64 {
+            let mut next = vec![BigInt::one(); n as usize + 1];
+            for k in 1..n as usize {
+                next[k] = &row[k - 1] + &row[k];
+            }
+            row = next;
+
+            for (k, e) in row.iter().enumerate() {
+                assert_eq!(&BigInt::binomial(n, k as u64), e, "{n} {k}");
+            }
+        }
+    }
+
+    #[test]
+    fn binomial_edges() {
+        assert_eq!(BigInt::binomial(0, 0), 1);
+        assert_eq!(BigInt::binomial(5, 6), 0);
+        assert_eq!(BigInt::binomial(u64::MAX, 1), u64::MAX);
+        assert_eq!(
+            BigInt::binomial(1000, 500),
+            BigInt::factorial(1000) / (BigInt::factorial(500) * BigInt::factorial(500))
+        );
+
+        // either side of where the sieve stops being cheap for every `k`
+        let n = BINOMIAL_SIEVE_CHEAP;
+        assert_eq!(
+            BigInt::binomial(n + 1, 100) * (n + 1 - 100),
+            BigInt::binomial(n, 100) * (n + 1)
+        );
+
+        let n = 10_000_000_000;
+        let expected = (16..=20).fold(BigInt::binomial(n, 15), |acc, i| acc * (n - i + 1) / i);
+        assert_eq!(BigInt::binomial(n, 20), expected);
+        assert_eq!(
+            BigInt::binomial(u64::MAX, 1000) * (u64::MAX - 1000),
+            BigInt::binomial(u64::MAX, 1001) * 1001u16
+        );
+    }
+
+    #[test]
+    fn multinomial() {
+        assert_eq!(BigInt::multinomial(&[]), 1);
+        assert_eq!(BigInt::multinomial(&[5]), 1);
+        assert_eq!(BigInt::multinomial(&[2, 3, 4]), 1260);
+        assert_eq!(BigInt::multinomial(&[1, 1, 1, 1]), 24);
+        assert_eq!(BigInt::multinomial(&[0, 7]), 1);
+    }
+
+    #[test]
+    fn fibonacci() {
+        let (mut a, mut b) = (BigInt::zero(), BigInt::one());
+        for n in 0..300 {
+            assert_eq!(BigInt::fibonacci(n), a, "{n}");
+            (a, b) = (b.clone(), a + b);
+        }
+    }
+
+    #[test]
+    fn lucas() {
+        let (mut a, mut b) = (BigInt::from(2), BigInt::one());
+        for n in 0..300 {
+            assert_eq!(BigInt::lucas(n), a, "{n}");
+            (a, b) = (b.clone(), a + b);
+        }
+    }
+
+    #[test]
+    fn fibonacci_identities() {
+        // F_2n = F_n L_n, and L_n^2 - 5 F_n^2 = 4 (-1)^n
+        let n = 10_001;
+        let (f, l) = (BigInt::fibonacci(n), BigInt::lucas(n));
+        assert_eq!(BigInt::fibonacci(2 * n), &f * &l);
+        assert_eq!(&l * &l - &f * &f * 5u8, -4);
+    }
+
+    #[test]
+    fn primorial() {
+        assert_eq!(BigInt::primorial(0), 1);
+        assert_eq!(BigInt::primorial(2), 2);
+        assert_eq!(BigInt::primorial(30), 6_469_693_230u64);
+        assert_eq!(BigInt::primorial(31), 6_469_693_230u64 * 31);
+        assert_eq!(
+            BigInt::primorial(1000),
+            primes_to(1000)
+                .into_iter()
+                .fold(BigInt::one(), |acc, p| acc * p)
+        );
+    }
+}
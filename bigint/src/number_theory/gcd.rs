@@ -0,0 +1,136 @@
+use std::mem;
+
+use crate::BigInt;
+
+impl BigInt {
+    /// Calculates the greatest common divisor of `self` and `other`. The result is never negative,
+    /// and `gcd(0, 0)` is `0`.
+    pub fn gcd(&self, other: &BigInt) -> BigInt {
+        let mut a = self.abs();
+        let mut b = other.abs();
+
+        while b != 0 {
+            a %= &b;
+            mem::swap(&mut a, &mut b);
+        }
+        a
+    }
+
+    /// Calculates the greatest common divisor of `self` and `other`, along with Bézout
+    /// coefficients `(g, x, y)` such that `self * x + other * y == g`.
+    pub fn extended_gcd(&self, other: &BigInt) -> (BigInt, BigInt, BigInt) {
+        let (mut r0, mut r1) = (self.abs(), other.abs());
+        let (mut x0, mut x1) = (BigInt::one(), BigInt::zero());
+        let (mut y0, mut y1) = (BigInt::zero(), BigInt::one());
+
+        while r1 != 0 {
+            let (q, r) = r0.div_rem(r1.clone());
+
+            (r0, r1) = (r1, r);
+            (x0, x1) = (x1.clone(), x0 - &q * &x1);
+            (y0, y1) = (y1.clone(), y0 - &q * &y1);
+        }
+
+        x0.signed ^= self.signed;
+        y0.signed ^= other.signed;
+        (r0, x0, y0)
+    }
+
+    /// Calculates the least common multiple of `self` and `other`. The result is never negative,
+    /// and is `0` if either value is `0`.
+    pub fn lcm(&self, other: &BigInt) -> BigInt {
+        if *self == 0 || *other == 0 {
+            BigInt::zero()
+        } else {
+            self.abs() / self.gcd(other) * other.abs()
+        }
+    }
+
+    /// Calculates the multiplicative inverse of `self` within the modulus `m`, in the range
+    /// `0..m.abs()`. Returns `None` if `self` and `m` are not coprime.
+    pub fn mod_inverse(&self, m: &BigInt) -> Option<BigInt> {
+        if *m == 0 {
+            return None;
+        }
+
+        let (g, x, _) = self.modulo(m).extended_gcd(m);
+        if g == 1 {
+            Some(x.modulo(m))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! test_gcd {
+        ($name:ident: $a:expr, $b:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                let (a, b) = (BigInt::from($a), BigInt::from($b));
+                let (g, x, y) = a.extended_gcd(&b);
+
+                assert_eq!(a.gcd(&b), $e);
+                assert_eq!(g, $e);
+                assert_eq!(a * x + b * y, g);
+            }
+        };
+    }
+
+    test_gcd!(zero_zero: 0, 0, 0);
+
+    test_gcd!(zero: 0, 12, 12);
+
+    test_gcd!(coprime: 35, 64, 1);
+
+    test_gcd!(common: 462, 1071, 21);
+
+    test_gcd!(neg_left: -462, 1071, 21);
+
+    test_gcd!(neg_both: -462, -1071, 21);
+
+    test_gcd!(big: u128::MAX, u64::MAX as u128 * 0x1234_5678, u64::MAX);
+
+    #[test]
+    fn lcm() {
+        assert_eq!(BigInt::from(4).lcm(&BigInt::from(6)), 12);
+        assert_eq!(BigInt::from(-4).lcm(&BigInt::from(6)), 12);
+        assert_eq!(BigInt::from(0).lcm(&BigInt::from(6)), 0);
+        assert_eq!(
+            BigInt::from(u64::MAX).lcm(&BigInt::from(u32::MAX)),
+            u64::MAX
+        );
+    }
+
+    macro_rules! test_mod_inverse {
+        ($name:ident: $a:expr, $m:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                let m = BigInt::from($m);
+                let inv = BigInt::from($a).mod_inverse(&m);
+                assert_eq!(inv, $e.map(BigInt::from));
+
+                if let Some(inv) = inv {
+                    assert_eq!((inv * BigInt::from($a)).modulo(&m), BigInt::one().modulo(&m));
+                }
+            }
+        };
+    }
+
+    test_mod_inverse!(small: 3, 11, Some(4));
+
+    test_mod_inverse!(negative: -3, 11, Some(7));
+
+    test_mod_inverse!(neg_modulus: 3, -11, Some(4));
+
+    test_mod_inverse!(rsa: 65537, 3120, Some(2753));
+
+    test_mod_inverse!(not_coprime: 6, 9, None::<i32>);
+
+    test_mod_inverse!(zero_modulus: 3, 0, None::<i32>);
+
+    test_mod_inverse!(mod_one: 3, 1, Some(0));
+}
@@ -0,0 +1,361 @@
+use random::{Rng, RngOutput};
+
+use crate::arithmetic::montgomery::Montgomery;
+use crate::arithmetic::mul::sliding_window;
+use crate::{Base, BigInt};
+
+// The number of primes below 1024.
+const SMALL_PRIMES_LEN: usize = 172;
+
+// The primes below 1024, used for trial division before the probabilistic tests.
+const SMALL_PRIMES: [u16; SMALL_PRIMES_LEN] = small_primes();
+
+// Every composite below this has a factor in `SMALL_PRIMES`, since 1031 is the next prime.
+const TRIAL_DIVISION_LIMIT: u32 = 1031 * 1031;
+
+const fn small_primes() -> [u16; SMALL_PRIMES_LEN] {
+    let mut primes = [0; SMALL_PRIMES_LEN];
+    let (mut n, mut i) = (2, 0);
+
+    while i < SMALL_PRIMES_LEN {
+        let mut d = 2;
+        while d * d <= n && n % d != 0 {
+            d += 1;
+        }
+        if d * d > n {
+            primes[i] = n;
+            i += 1;
+        }
+        n += 1;
+    }
+    primes
+}
+
+// The number of Miller-Rabin rounds for a random candidate of the given size to be composite with a
+// probability below 2^-80, from the Handbook of Applied Cryptography, table 4.4.
+fn rounds(bits: usize) -> usize {
+    match bits {
+        0..=99 => 40,
+        100..=149 => 27,
+        150..=199 => 18,
+        200..=249 => 15,
+        250..=299 => 12,
+        300..=349 => 9,
+        350..=399 => 8,
+        400..=449 => 7,
+        450..=549 => 6,
+        550..=649 => 5,
+        650..=849 => 4,
+        850..=1299 => 3,
+        _ => 2,
+    }
+}
+
+impl BigInt {
+    /// Tests whether `self` is prime using trial division by small primes, followed by `rounds`
+    /// rounds of the Miller-Rabin test with random bases drawn from `rng`.
+    ///
+    /// A composite passes each round with a probability of at most 1/4, so a `true` result is
+    /// wrong with a probability of at most `4^-rounds`. A `false` result is always correct.
+    pub fn is_probable_prime<R: Rng>(&self, rounds: usize, rng: &mut R) -> bool
+    where
+        Base: RngOutput<R>,
+    {
+        if let Some(prime) = self.trial_division() {
+            return prime;
+        }
+
+        // bases are drawn from `2..(self - 1)`
+        let bound = self.clone() - 3u8;
+        (0..rounds).all(|_| self.miller_rabin(&(BigInt::random_below(&bound, rng) + 2)))
+    }
+
+    /// Tests whether `self` is prime using the Baillie-PSW test, which is a base 2 strong
+    /// probable prime test followed by a strong Lucas probable prime test.
+    ///
+    /// The test is deterministic, is known to be correct for every value below `2^64`, and has no
+    /// known counterexamples.
+    pub fn is_probable_prime_bpsw(&self) -> bool {
+        if let Some(prime) = self.trial_division() {
+            return prime;
+        }
+
+        self.miller_rabin(&BigInt::from(2)) && self.strong_lucas()
+    }
+
+    /// Generates a random prime with exactly `bits` bits, drawing candidates from `rng`. The top
+    /// two bits are always set, so the product of two such primes has exactly `2 * bits` bits.
+    /// Panics if `bits` is less than `2`.
+    pub fn random_prime<R: Rng>(bits: usize, rng: &mut R) -> Self
+    where
+        Base: RngOutput<R>,
+    {
+        assert!(bits >= 2, "a prime has at least 2 bits");
+
+        loop {
+            let p = BigInt::random_candidate(bits, rng);
+            if p.is_probable_prime_bpsw() && p.is_probable_prime(rounds(bits), rng) {
+                return p;
+            }
+        }
+    }
+
+    /// Generates a random safe prime `p = 2q + 1`, where `q` is also prime, with exactly `bits`
+    /// bits, drawing candidates from `rng`. As with `random_prime`, the top two bits are always
+    /// set. Panics if `bits` is less than `3`.
+    pub fn random_safe_prime<R: Rng>(bits: usize, rng: &mut R) -> Self
+    where
+        Base: RngOutput<R>,
+    {
+        assert!(bits >= 3, "a safe prime has at least 3 bits");
+
+        loop {
+            let q = BigInt::random_candidate(bits - 1, rng);
+            let p = (q.clone() << 1u8) + 1u8;
+
+            if q.trial_division() != Some(false)
+                && p.trial_division() != Some(false)
+                && q.is_probable_prime_bpsw()
+                && p.is_probable_prime_bpsw()
+                && q.is_probable_prime(rounds(bits), rng)
+            {
+                return p;
+            }
+        }
+    }
+
+    // A random odd value with exactly `bits` bits and the top two bits set.
+    fn random_candidate<R: Rng>(bits: usize, rng: &mut R) -> Self
+    where
+        Base: RngOutput<R>,
+    {
+        let top = BigInt::from(3) << (bits - 2);
+        BigInt::random_bits(bits, rng) | top | BigInt::one()
+    }
+
+    // Decides primality if `self` is small or has a small factor.
+    fn trial_division(&self) -> Option<bool> {
+        if *self < 2 {
+            return Some(false);
+        }
+
+        for p in SMALL_PRIMES {
+            if *self == p {
+                return Some(true);
+            } else if self.rem_limb(p as Base) == 0 {
+                return Some(false);
+            }
+        }
+
+        if *self < TRIAL_DIVISION_LIMIT {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    // The strong probable prime test to base `a`, for an odd `self` greater than `a`.
+    //
+    // Writes `self - 1 = d * 2^s` with `d` odd, and checks that `a^d = 1` or that
+    // `a^(d * 2^r) = -1` for some `r < s`, as there are no other square roots of `1` modulo a
+    // prime.
+    fn miller_rabin(&self, a: &BigInt) -> bool {
+        let n_1 = self.clone() - 1u8;
+        let s = n_1.trailing_zeros();
+        let d = n_1.clone() >> s;
+
+        let mont = Montgomery::new(self);
+        let one = mont.one();
+        let neg_one = mont.encode(&n_1);
+
+        let mut x = sliding_window(mont.encode(a), &d, one.clone(), |a, b| mont.mul(a, b));
+        if x == one || x == neg_one {
+            return true;
+        }
+
+        for _ in 1..s {
+            x = mont.mul(&x, &x);
+            if x == neg_one {
+                return true;
+            } else if x == one {
+                return false;
+            }
+        }
+        false
+    }
+
+    // The strong Lucas probable prime test, for an odd `self` with no small factors.
+    //
+    // Uses Selfridge's parameters, `P = 1` and `Q = (1 - D) / 4` with `D` the first of
+    // `5, -7, 9, -11, ...` where the Jacobi symbol `(D / self) = -1`. Writes `self + 1 = d * 2^s`
+    // with `d` odd, and checks that `U_d = 0` or that `V_(d * 2^r) = 0` for some `r < s`.
+    fn strong_lucas(&self) -> bool {
+        const BITS: usize = Base::BITS as usize;
+        let n = self;
+
+        let mut d = BigInt::from(5);
+        loop {
+            match d.jacobi(n) {
+                -1 => break,
+                0 => return false,
+                _ => {}
+            }
+
+            // a square never gives -1, so the search would never end
+            if d == 13 && n.is_perfect_square() {
+                return false;
+            }
+
+            d += if d.is_positive() { 2 } else { -2 };
+            d.signed ^= true;
+        }
+
+        let q = (BigInt::one() - &d) / 4u8;
+        let (d, q) = (d.modulo(n), q.modulo(n));
+
+        let n1 = n.clone() + 1u8;
+        let s = n1.trailing_zeros();
+        let k = n1 >> s;
+        let bit = |i: usize| k.data[i / BITS] >> (i % BITS) & 1 == 1;
+
+        // divides by 2 within the odd modulus
+        let half = |x: BigInt| if x.is_odd() { x + n } else { x } >> 1u8;
+
+        // U_1 = 1, V_1 = P, and Q^1
+        let (mut u, mut v, mut qk) = (BigInt::one(), BigInt::one(), q.clone());
+
+        for i in (0..(k.bits() - 1)).rev() {
+            // U_2k = U_k * V_k, V_2k = V_k^2 - 2 * Q^k
+            u = (&u * &v) % n;
+            v = (&v * &v - (qk.clone() << 1u8)).modulo(n);
+            qk = (&qk * &qk) % n;
+
+            if bit(i) {
+                // U_(k+1) = (P * U_k + V_k) / 2, V_(k+1) = (D * U_k + P * V_k) / 2
+                (u, v) = (half((&u + &v) % n), half((&d * &u + &v) % n));
+                qk = (&qk * &q) % n;
+            }
+        }
+
+        if u == 0 || v == 0 {
+            return true;
+        }
+
+        for _ in 1..s {
+            v = (&v * &v - (qk.clone() << 1u8)).modulo(n);
+            if v == 0 {
+                return true;
+            }
+            qk = (&qk * &qk) % n;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mersenne_twister::MersenneTwister;
+
+    fn is_prime(n: u32) -> bool {
+        n >= 2 && (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)
+    }
+
+    #[test]
+    fn small_primes() {
+        assert_eq!(SMALL_PRIMES[..5], [2, 3, 5, 7, 11]);
+        assert_eq!(SMALL_PRIMES[SMALL_PRIMES_LEN - 1], 1021);
+        assert!(SMALL_PRIMES.iter().all(|&p| is_prime(p as u32)));
+    }
+
+    macro_rules! test_prime {
+        ($name:ident: $n:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                let n: BigInt = $n;
+                let mut rng = MersenneTwister::from(0x5eed);
+
+                assert_eq!(n.is_probable_prime(20, &mut rng), $e, "miller-rabin");
+                assert_eq!(n.is_probable_prime_bpsw(), $e, "baillie-psw");
+            }
+        };
+    }
+
+    test_prime!(zero: BigInt::zero(), false);
+
+    test_prime!(one: BigInt::one(), false);
+
+    test_prime!(two: BigInt::from(2), true);
+
+    test_prime!(negative: BigInt::from(-7), false);
+
+    test_prime!(largest_small: BigInt::from(1021), true);
+
+    test_prime!(carmichael: BigInt::from(561), false);
+
+    test_prime!(fermat_4: BigInt::from(65537), true);
+
+    test_prime!(mersenne_61: (BigInt::one() << 61u8) - 1, true);
+
+    test_prime!(mersenne_127: (BigInt::one() << 127u8) - 1, true);
+
+    test_prime!(mersenne_521: (BigInt::one() << 521u16) - 1, true);
+
+    test_prime!(mersenne_composite: (BigInt::one() << 67u8) - 1, false);
+
+    test_prime!(square: BigInt::from(1_000_003u64 * 1_000_003), false);
+
+    test_prime!(semiprime:
+        ((BigInt::one() << 61u8) - 1) * ((BigInt::one() << 89u8) - 1),
+        false
+    );
+
+    // strong pseudoprimes to base 2, which the lucas test must reject
+    test_prime!(spsp_2: BigInt::from(3_215_031_751u64), false);
+
+    test_prime!(spsp_2_large: BigInt::from(318_665_857_834_031_151_167_461u128), false);
+
+    #[test]
+    fn against_trial_division() {
+        for n in (TRIAL_DIVISION_LIMIT..(TRIAL_DIVISION_LIMIT + 4000)).step_by(2) {
+            let i = BigInt::from(n);
+            assert_eq!(i.miller_rabin(&BigInt::from(2)) && i.strong_lucas(), is_prime(n), "{n}");
+        }
+    }
+
+    #[test]
+    fn pseudoprimes() {
+        // strong pseudoprimes to base 2
+        for n in [2047, 3277, 4033, 4681, 8321] {
+            assert!(BigInt::from(n).miller_rabin(&BigInt::from(2)), "{n}");
+        }
+
+        // strong lucas pseudoprimes
+        for n in [5459, 5777, 10877, 16109, 18971] {
+            assert!(BigInt::from(n).strong_lucas(), "{n}");
+        }
+    }
+
+    #[test]
+    fn random_prime() {
+        let mut rng = MersenneTwister::from(0x5eed);
+
+        for bits in [2, 3, 16, 64, 65, 256] {
+            let p = BigInt::random_prime(bits, &mut rng);
+            assert_eq!(p.bits(), bits);
+            assert!(p.is_probable_prime_bpsw(), "{p}");
+        }
+    }
+
+    #[test]
+    fn random_safe_prime() {
+        let mut rng = MersenneTwister::from(0x5eed);
+
+        for bits in [3, 16, 64, 128] {
+            let p = BigInt::random_safe_prime(bits, &mut rng);
+            assert_eq!(p.bits(), bits);
+            assert!(p.is_probable_prime_bpsw(), "{p}");
+            assert!(((p - 1u8) >> 1u8).is_probable_prime_bpsw());
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use crate::BigInt;
+
+// Multiplies `a + b * sqrt(w)` by `c + d * sqrt(w)` modulo `p`.
+fn mul_ext(a: &(BigInt, BigInt), b: &(BigInt, BigInt), w: &BigInt, p: &BigInt) -> (BigInt, BigInt) {
+    (
+        (&a.0 * &b.0 + &a.1 * &b.1 % p * w) % p,
+        (&a.0 * &b.1 + &a.1 * &b.0) % p,
+    )
+}
+
+impl BigInt {
+    /// Calculates the Jacobi symbol `(self / n)`, which is `0`, `1` or `-1`. Panics if `n` is not
+    /// odd and positive.
+    pub fn jacobi(&self, n: &BigInt) -> i8 {
+        assert!(
+            n.is_positive() && n.is_odd(),
+            "the Jacobi symbol is only defined for an odd positive modulus"
+        );
+
+        let mut a = self.modulo(n);
+        let mut n = n.clone();
+        let mut t = 1;
+
+        while a != 0 {
+            let z = a.trailing_zeros();
+            a >>= z;
+
+            // (2 / n) = -1 when n = 3 or 5 (mod 8)
+            let r = n.rem_limb(8);
+            if z % 2 == 1 && (r == 3 || r == 5) {
+                t = -t;
+            }
+
+            // quadratic reciprocity flips the sign when both are 3 (mod 4)
+            if a.rem_limb(4) == 3 && r % 4 == 3 {
+                t = -t;
+            }
+
+            (a, n) = (n % &a, a);
+        }
+
+        if n == 1 {
+            t
+        } else {
+            0
+        }
+    }
+
+    /// Calculates the Legendre symbol `(self / p)` for an odd prime `p`, which is `1` when `self`
+    /// is a square modulo `p`, `-1` when it is not, and `0` when `p` divides it. This is the same
+    /// as the Jacobi symbol, and `p` is not checked to be prime.
+    pub fn legendre(&self, p: &BigInt) -> i8 {
+        self.jacobi(p)
+    }
+
+    /// Calculates a square root of `self` modulo the prime `p`, in the range `0..p`, or `None` if
+    /// there is none. The other root is `p` minus this one.
+    ///
+    /// Uses the direct formulas when `p = 3 (mod 4)` or `p = 5 (mod 8)`, then Tonelli-Shanks, or
+    /// Cipolla's algorithm when `p - 1` is divisible by a large power of two which would make
+    /// Tonelli-Shanks slow. If `p` is not prime, `None` is returned when no root was found, even
+    /// though one may exist.
+    pub fn sqrt_mod(&self, p: &BigInt) -> Option<BigInt> {
+        assert!(p.is_positive(), "the modulus must be positive");
+
+        let a = self.modulo(p);
+        if *p == 2 || a == 0 {
+            return Some(a);
+        }
+        if p.is_even() || a.legendre(p) != 1 {
+            return None;
+        }
+
+        let x = if p.rem_limb(4) == 3 {
+            a.modpow(&((p.clone() + 1u8) >> 2u8), p)
+        } else if p.rem_limb(8) == 5 {
+            // Atkin's formula, with `i = 2a * v^2` a square root of -1
+            let a2 = (a.clone() << 1u8) % p;
+            let v = a2.modpow(&((p.clone() - 5u8) >> 3u8), p);
+            let i = &a2 * &v % p * &v % p;
+            (&a * &v % p * (i + p - 1u8)) % p
+        } else {
+            let s = (p.clone() - 1u8).trailing_zeros();
+            if s * s > 8 * p.bits() {
+                a.cipolla(p)
+            } else {
+                a.tonelli_shanks(p, s)
+            }
+        };
+
+        if (&x * &x) % p == a {
+            Some(x)
+        } else {
+            None
+        }
+    }
+
+    // Tonelli-Shanks, for a square `self` within `0..p` and `p - 1 = q * 2^s` with `q` odd.
+    fn tonelli_shanks(&self, p: &BigInt, s: usize) -> BigInt {
+        let q = (p.clone() - 1u8) >> s;
+
+        let mut z = BigInt::from(2);
+        while z.legendre(p) != -1 {
+            z += 1u8;
+        }
+
+        let mut m = s;
+        let mut c = z.modpow(&q, p);
+        let mut t = self.modpow(&q, p);
+        let mut r = self.modpow(&((q + 1u8) >> 1u8), p);
+
+        // keeps `r^2 = self * t`, while the order of `t` halves each step
+        while t != 1 {
+            let mut i = 0;
+            let mut t2 = t.clone();
+            while t2 != 1 && i < m {
+                t2 = &t2 * &t2 % p;
+                i += 1;
+            }
+            if i == m {
+                break;
+            }
+
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = &b * &b % p;
+            }
+
+            m = i;
+            c = &b * &b % p;
+            t = t * &c % p;
+            r = r * b % p;
+        }
+        r
+    }
+
+    // Cipolla's algorithm, for a square `self` within `0..p`, which calculates
+    // `(t + sqrt(w))^((p + 1) / 2)` where `w = t^2 - self` is not a square.
+    fn cipolla(&self, p: &BigInt) -> BigInt {
+        let mut t = BigInt::one();
+        let w = loop {
+            let w = (&t * &t + p - self) % p;
+            if w.legendre(p) == -1 {
+                break w;
+            }
+            t += 1u8;
+        };
+
+        let exp = (p.clone() + 1u8) >> 1u8;
+        let mut base = (t, BigInt::one());
+        let mut acc = (BigInt::one(), BigInt::zero());
+
+        for i in 0..exp.bits() {
+            if exp.bit(i) {
+                acc = mul_ext(&acc, &base, &w, p);
+            }
+            base = mul_ext(&base, &base, &w, p);
+        }
+        acc.0
+    }
+}
+
+/// Solves a system of congruences `x = r (mod m)` given as `(r, m)` pairs, with the Chinese
+/// remainder theorem. Returns the smallest non-negative `x` along with the least common multiple
+/// of the moduli, so all solutions are `x` plus multiples of it, or `None` if the congruences
+/// contradict each other. The moduli do not need to be coprime. Panics if a modulus is not
+/// positive.
+pub fn crt(congruences: &[(BigInt, BigInt)]) -> Option<(BigInt, BigInt)> {
+    let mut x = BigInt::zero();
+    let mut lcm = BigInt::one();
+
+    for (r, m) in congruences {
+        assert!(m.is_positive(), "the modulus must be positive");
+
+        // x + lcm * k = r (mod m), so lcm * k = r - x (mod m), which needs g = gcd(lcm, m) to
+        // divide r - x
+        let g = lcm.gcd(m);
+        let (diff, rem) = (r - &x).div_rem(g.clone());
+        if rem != 0 {
+            return None;
+        }
+
+        let m = m / &g;
+        let k = (diff * (&lcm / &g).mod_inverse(&m).unwrap()).modulo(&m);
+        x += &lcm * k;
+        lcm *= m;
+    }
+    Some((x.modulo(&lcm), lcm))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jacobi() {
+        // (a / 15) for a in 0..15
+        let expected = [0, 1, 1, 0, 1, 0, 0, -1, 1, 0, 0, -1, 0, -1, -1];
+        for (a, &e) in expected.iter().enumerate() {
+            assert_eq!(BigInt::from(a).jacobi(&BigInt::from(15)), e, "{a}");
+        }
+        assert_eq!(BigInt::from(-1).jacobi(&BigInt::from(7)), -1);
+        assert_eq!(BigInt::from(5).jacobi(&BigInt::one()), 1);
+    }
+
+    #[test]
+    #[should_panic = "the Jacobi symbol is only defined for an odd positive modulus"]
+    fn jacobi_even() {
+        BigInt::from(3).jacobi(&BigInt::from(10));
+    }
+
+    #[test]
+    fn legendre() {
+        let p = BigInt::from(23);
+        for a in 0..23 {
+            let square = (1..23).any(|x| x * x % 23 == a);
+            let e = if a == 0 {
+                0
+            } else if square {
+                1
+            } else {
+                -1
+            };
+            assert_eq!(BigInt::from(a).legendre(&p), e, "{a}");
+        }
+    }
+
+    macro_rules! test_sqrt_mod {
+        ($name:ident: $p:expr) => {
+            #[test]
+            fn $name() {
+                let p = BigInt::from($p);
+                for a in 0..200u32 {
+                    let a = BigInt::from(a);
+                    match a.sqrt_mod(&p) {
+                        Some(x) => {
+                            assert!(x < p);
+                            assert_eq!((&x * &x).modulo(&p), a.modulo(&p), "{a}");
+                        }
+                        None => assert_eq!(a.legendre(&p), -1, "{a}"),
+                    }
+                }
+            }
+        };
+    }
+
+    // 3 (mod 4)
+    test_sqrt_mod!(p_3_mod_4: 1_000_003);
+
+    // 5 (mod 8)
+    test_sqrt_mod!(p_5_mod_8: 1_000_037);
+
+    // 1 (mod 8), with p - 1 = 2^4 * q
+    test_sqrt_mod!(tonelli_shanks: 1_000_081);
+
+    // p - 1 = 2^16 * 1, large enough to use Cipolla
+    test_sqrt_mod!(cipolla: 65_537);
+
+    test_sqrt_mod!(two: 2);
+
+    #[test]
+    fn sqrt_mod_large() {
+        // 2^255 - 19
+        let p = (BigInt::one() << 255u8) - 19u8;
+        let a = BigInt::from(123_456_789u32);
+        let x = (&a * &a).sqrt_mod(&p).unwrap();
+        assert!(x == a || x == &p - &a);
+
+        // 2^224 - 2^96 + 1, the P-224 prime, with p - 1 divisible by 2^96
+        let p = (BigInt::one() << 224u8) - (BigInt::one() << 96u8) + 1u8;
+        let x = (&a * &a).sqrt_mod(&p).unwrap();
+        assert!(x == a || x == p - a);
+    }
+
+    #[test]
+    fn sqrt_mod_none() {
+        assert_eq!(BigInt::from(3).sqrt_mod(&BigInt::from(7)), None);
+        assert!(BigInt::from(2).sqrt_mod(&BigInt::from(65_537)).is_some());
+        assert_eq!(BigInt::from(3).sqrt_mod(&BigInt::from(65_537)), None);
+    }
+
+    macro_rules! test_crt {
+        ($name:ident: [$(($r:expr, $m:expr)),*], $e:expr) => {
+            #[test]
+            fn $name() {
+                let congruences = [$((BigInt::from($r), BigInt::from($m))),*];
+                let e: Option<(i32, i32)> = $e;
+                assert_eq!(crt(&congruences), e.map(|(x, m)| (BigInt::from(x), BigInt::from(m))));
+            }
+        };
+    }
+
+    test_crt!(empty: [], Some((0, 1)));
+
+    test_crt!(coprime: [(2, 3), (3, 5), (2, 7)], Some((23, 105)));
+
+    test_crt!(negative: [(-1, 3), (-1, 5)], Some((14, 15)));
+
+    test_crt!(shared: [(2, 4), (4, 6)], Some((10, 12)));
+
+    test_crt!(contradiction: [(1, 4), (2, 6)], None);
+
+    #[test]
+    fn crt_large() {
+        let m = [
+            (BigInt::one() << 127u8) - 1u8,
+            (BigInt::one() << 89u8) - 1u8,
+            (BigInt::one() << 61u8) - 1u8,
+        ];
+        let x = BigInt::from(0x1234_5678_9abc_def0_u64).pow(5u8);
+        let congruences = m
+            .iter()
+            .map(|m| (x.modulo(m), m.clone()))
+            .collect::<Vec<_>>();
+        let (y, lcm) = crt(&congruences).unwrap();
+        assert_eq!(lcm, &m[0] * &m[1] * &m[2]);
+        assert_eq!(y, x.modulo(&lcm));
+    }
+}
@@ -0,0 +1,145 @@
+//! Generation of random `BigInt`s from the `random` crate's generators.
+
+use std::ops::Range;
+
+use random::{Rng, RngOutput};
+
+use crate::{Base, BigInt};
+
+impl BigInt {
+    /// Generates a uniformly random value in `0..2^bits`, drawing whole limbs from `rng`.
+    pub fn random_bits<R: Rng>(bits: usize, rng: &mut R) -> Self
+    where
+        Base: RngOutput<R>,
+    {
+        const BITS: usize = Base::BITS as usize;
+
+        let mut data = rng.get_n::<Base>((bits + BITS - 1) / BITS);
+        if bits % BITS != 0 {
+            let last = data.len() - 1;
+            data[last] &= Base::MAX >> (BITS - bits % BITS);
+        }
+
+        BigInt::from_limbs(data)
+    }
+
+    /// Generates a uniformly random value in `0..bound`. Values with as many bits as `bound` are
+    /// drawn until one is in range, which avoids the bias of reducing a larger value, and takes
+    /// fewer than two draws on average. Panics if `bound` is not positive.
+    pub fn random_below<R: Rng>(bound: &BigInt, rng: &mut R) -> Self
+    where
+        Base: RngOutput<R>,
+    {
+        assert!(bound.is_positive(), "bound must be positive");
+
+        loop {
+            let x = BigInt::random_bits(bound.bits(), rng);
+            if x < *bound {
+                return x;
+            }
+        }
+    }
+
+    /// Generates a uniformly random value in `range`. Panics if the range is empty.
+    pub fn random_range<R: Rng>(range: Range<BigInt>, rng: &mut R) -> Self
+    where
+        Base: RngOutput<R>,
+    {
+        assert!(range.start < range.end, "cannot sample an empty range");
+        BigInt::random_below(&(&range.end - &range.start), rng) + range.start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chacha::rng::ChaChaRng;
+    use mersenne_twister::MersenneTwister;
+
+    // Draws from every `random_*` function and checks the results are in range and cover it.
+    fn check<R: Rng>(rng: &mut R)
+    where
+        Base: RngOutput<R>,
+    {
+        for bits in [0, 1, 7, 64, 65, 200] {
+            for _ in 0..20 {
+                let x = BigInt::random_bits(bits, rng);
+                assert!(x.bits() <= bits && !x.is_negative());
+            }
+        }
+
+        let bound = BigInt::from(10);
+        let mut seen = [false; 10];
+        for _ in 0..500 {
+            let x = BigInt::random_below(&bound, rng);
+            seen[usize::try_from(x).unwrap()] = true;
+        }
+        assert!(seen.iter().all(|&e| e), "not every value below the bound was drawn");
+
+        let lo = BigInt::from(-5) << 100u8;
+        let hi = BigInt::from(3) << 100u8;
+        for _ in 0..100 {
+            let x = BigInt::random_range(lo.clone()..hi.clone(), rng);
+            assert!(lo <= x && x < hi);
+        }
+
+        let x = BigInt::random_range(BigInt::from(-3)..BigInt::from(-2), rng);
+        assert_eq!(x, -3);
+    }
+
+    #[test]
+    #[cfg(target_family = "unix")]
+    fn os_rng() {
+        check(&mut random::OsRng::new());
+    }
+
+    #[test]
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64", target_feature = "rdrand"))]
+    fn cpu_rng() {
+        check(&mut random::CpuRng::new());
+    }
+
+    #[test]
+    fn mersenne_twister() {
+        check(&mut MersenneTwister::from(0x5eed));
+    }
+
+    #[test]
+    fn chacha() {
+        check(&mut ChaChaRng::from([0x5eed; 8]));
+    }
+
+    #[test]
+    fn reproducible() {
+        let bound = BigInt::one() << 1000u16;
+        let a = BigInt::random_below(&bound, &mut MersenneTwister::from(42));
+        let b = BigInt::random_below(&bound, &mut MersenneTwister::from(42));
+
+        assert_eq!(a, b);
+        assert_ne!(a, BigInt::random_below(&bound, &mut MersenneTwister::from(43)));
+    }
+
+    #[test]
+    fn uniform() {
+        // chi-squared over 16 buckets, 30.58 is the critical value at p = 0.01
+        let mut rng = ChaChaRng::from([1; 8]);
+        let bound = BigInt::from(16 * 3);
+        let n = 16_000;
+        let mut buckets = [0f64; 16];
+
+        for _ in 0..n {
+            let x = u8::try_from(BigInt::random_below(&bound, &mut rng)).unwrap();
+            buckets[x as usize % 16] += 1.0;
+        }
+
+        let expected = n as f64 / 16.0;
+        let chi_sqrd: f64 = buckets.iter().map(|e| (e - expected).powi(2) / expected).sum();
+        assert!(chi_sqrd < 30.58, "{chi_sqrd}");
+    }
+
+    #[test]
+    #[should_panic = "cannot sample an empty range"]
+    fn empty_range() {
+        BigInt::random_range(BigInt::one()..BigInt::one(), &mut MersenneTwister::new());
+    }
+}
@@ -0,0 +1,644 @@
+//! Exact rational numbers, as a fraction of two `BigInt`s.
+
+use std::cmp::Ordering;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+use std::str::FromStr;
+
+pub use crate::convert::TryFromFloatError;
+use crate::BigInt;
+
+/// A rational number, always kept in lowest terms with a positive denominator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BigRational {
+    num: BigInt,
+    den: BigInt,
+}
+
+#[derive(Debug)]
+/// An error generated when trying to parse a string into a `BigRational`.
+pub struct ParseRationalError(());
+
+impl Error for ParseRationalError {}
+
+impl Display for ParseRationalError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid rational value")
+    }
+}
+
+// Negates `x` in place, leaving zero positive.
+fn negate(mut x: BigInt) -> BigInt {
+    x.signed = !x.signed && x != 0;
+    x
+}
+
+impl BigRational {
+    /// Creates the rational `num / den` in lowest terms. Panics if `den` is zero.
+    pub fn new(num: BigInt, den: BigInt) -> Self {
+        assert!(
+            den != 0,
+            "attempt to create a rational with a zero denominator"
+        );
+
+        let (mut num, mut den) = if den.is_negative() {
+            (negate(num), negate(den))
+        } else {
+            (num, den)
+        };
+
+        let g = num.gcd(&den);
+        if g != 1 {
+            num /= &g;
+            den /= &g;
+        }
+        Self { num, den }
+    }
+
+    /// Creates a `BigRational` with the value of `0`.
+    pub fn zero() -> Self {
+        Self::from_integer(BigInt::zero())
+    }
+
+    /// Creates a `BigRational` with the value of `1`.
+    pub fn one() -> Self {
+        Self::from_integer(BigInt::one())
+    }
+
+    /// Creates a `BigRational` equal to the integer `i`.
+    pub fn from_integer(i: BigInt) -> Self {
+        Self {
+            num: i,
+            den: BigInt::one(),
+        }
+    }
+
+    /// The numerator, which carries the sign of the value.
+    pub fn numer(&self) -> &BigInt {
+        &self.num
+    }
+
+    /// The denominator, which is always positive.
+    pub fn denom(&self) -> &BigInt {
+        &self.den
+    }
+
+    /// Returns true if the value is a whole number.
+    pub fn is_integer(&self) -> bool {
+        self.den == 1
+    }
+
+    /// Returns true if `self` is greater than `0`.
+    pub fn is_positive(&self) -> bool {
+        self.num.is_positive()
+    }
+
+    /// Returns true if `self` is less than `0`.
+    pub fn is_negative(&self) -> bool {
+        self.num.is_negative()
+    }
+
+    /// Returns the absolute value of `self`.
+    pub fn abs(&self) -> Self {
+        Self {
+            num: self.num.abs(),
+            den: self.den.clone(),
+        }
+    }
+
+    /// Returns `1 / self`. Panics if `self` is zero.
+    pub fn recip(&self) -> Self {
+        Self::new(self.den.clone(), self.num.clone())
+    }
+
+    /// Raises `self` to the power of `exp`. Panics if `self` is zero and `exp` is negative.
+    pub fn pow(&self, exp: i32) -> Self {
+        let r = Self {
+            num: self.num.pow(exp.unsigned_abs()),
+            den: self.den.pow(exp.unsigned_abs()),
+        };
+        if exp < 0 {
+            r.recip()
+        } else {
+            r
+        }
+    }
+
+    /// Rounds towards negative infinity.
+    pub fn floor(&self) -> BigInt {
+        let (quot, rem) = self.num.clone().div_rem(self.den.clone());
+        if rem.is_negative() {
+            quot - 1u8
+        } else {
+            quot
+        }
+    }
+
+    /// Rounds towards positive infinity.
+    pub fn ceil(&self) -> BigInt {
+        let (quot, rem) = self.num.clone().div_rem(self.den.clone());
+        if rem.is_positive() {
+            quot + 1u8
+        } else {
+            quot
+        }
+    }
+
+    /// Rounds towards zero.
+    pub fn trunc(&self) -> BigInt {
+        &self.num / &self.den
+    }
+
+    /// Rounds to the nearest integer, with halves rounded away from zero.
+    pub fn round(&self) -> BigInt {
+        let half = Self::new(BigInt::one(), BigInt::from(2));
+        if self.is_negative() {
+            (self - half).ceil()
+        } else {
+            (self + half).floor()
+        }
+    }
+
+    /// Converts `self` into the nearest `f64`, rounding halfway cases to even. Values too large
+    /// for an `f64` become infinite.
+    pub fn to_f64(&self) -> f64 {
+        const MANTISSA: i64 = f64::MANTISSA_DIGITS as i64;
+        const MIN_EXP: i64 = f64::MIN_EXP as i64 - MANTISSA;
+
+        if self.num == 0 {
+            return 0.0;
+        }
+        let sign = if self.is_negative() { -1.0 } else { 1.0 };
+
+        // the value is within `2^(e - 1)..2^(e + 1)`
+        let (n, d) = (self.num.abs(), &self.den);
+        let e = n.bits() as i64 - d.bits() as i64;
+        if e > f64::MAX_EXP as i64 + 1 {
+            return sign * f64::INFINITY;
+        } else if e < MIN_EXP - 1 {
+            return sign * 0.0;
+        }
+
+        // scale so the quotient has at least two more bits than the result, and fold any
+        // remainder into the lowest bit so that it still breaks ties correctly
+        let s = -(e - MANTISSA - 2).max(MIN_EXP - 2);
+        let (q, r) = if s >= 0 {
+            (n << s as usize).div_rem(d.clone())
+        } else {
+            n.div_rem(d.clone() << (-s) as usize)
+        };
+        let mut q = u64::try_from(q).unwrap() | (r != 0) as u64;
+
+        // the position of the lowest bit which fits in the result, relative to the quotient
+        let top = 64 - q.leading_zeros() as i64 - 1 - s;
+        let shift = (top - (MANTISSA - 1)).max(MIN_EXP) + s;
+
+        let half = 1 << (shift - 1);
+        let rem = q & ((1 << shift) - 1);
+        q >>= shift;
+        if rem > half || rem == half && q & 1 == 1 {
+            q += 1;
+        }
+
+        let exp = shift - s;
+        let pow2 =
+            |e: i64| f64::from_bits(((e + f64::MAX_EXP as i64 - 1) as u64) << (MANTISSA - 1));
+        let val = if exp > f64::MAX_EXP as i64 - 1 {
+            f64::INFINITY
+        } else if exp < f64::MIN_EXP as i64 - 1 {
+            // the result is subnormal, so it is built from a normal value to avoid rounding twice
+            q as f64 * pow2(exp - (f64::MIN_EXP as i64 - 1)) * pow2(f64::MIN_EXP as i64 - 1)
+        } else {
+            q as f64 * pow2(exp)
+        };
+        sign * val
+    }
+
+    /// Formats the value as a decimal with `precision` digits after the point, rounding halfway
+    /// cases away from zero.
+    pub fn to_decimal_string(&self, precision: usize) -> String {
+        let scale = BigInt::from(10).pow(precision as u32);
+        let scaled = Self::new(&self.num * &scale, self.den.clone()).round();
+
+        let digits = scaled.abs().to_string();
+        let digits = format!("{:0>width$}", digits, width = precision + 1);
+        let (int, frac) = digits.split_at(digits.len() - precision);
+
+        let sign = if scaled.is_negative() { "-" } else { "" };
+        if precision == 0 {
+            format!("{sign}{int}")
+        } else {
+            format!("{sign}{int}.{frac}")
+        }
+    }
+}
+
+impl Default for BigRational {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl Ord for BigRational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.num * &other.den).cmp(&(&other.num * &self.den))
+    }
+}
+
+impl PartialOrd for BigRational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Displays the value as a fraction, or as a decimal when a precision is given, such as with
+/// `format!("{:.3}", r)`.
+impl Display for BigRational {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if let Some(precision) = f.precision() {
+            write!(f, "{}", self.to_decimal_string(precision))
+        } else if self.is_integer() {
+            write!(f, "{}", self.num)
+        } else {
+            write!(f, "{}/{}", self.num, self.den)
+        }
+    }
+}
+
+impl FromStr for BigRational {
+    type Err = ParseRationalError;
+
+    /// Parses a fraction such as `"-3/4"`, or a decimal such as `"0.125"`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let int = |s: &str| BigInt::from_str(s).map_err(|_| ParseRationalError(()));
+
+        if let Some((num, den)) = s.split_once('/') {
+            let den = int(den)?;
+            if den == 0 {
+                return Err(ParseRationalError(()));
+            }
+            Ok(Self::new(int(num)?, den))
+        } else if let Some((whole, frac)) = s.split_once('.') {
+            let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit() || c == '_');
+            let unsigned = whole.trim_start_matches(['-', '+']);
+            if !digits(frac) || !digits(unsigned) || unsigned.len() + frac.len() == 0 {
+                return Err(ParseRationalError(()));
+            }
+
+            // the extra zero keeps the digits non-empty for values like `".5"` and `"2."`
+            let sign = &whole[..whole.len() - unsigned.len()];
+            let num = int(&format!("{sign}{unsigned}{frac}0"))? / 10u8;
+            let den = BigInt::from(10).pow(frac.chars().filter(|&c| c != '_').count() as u32);
+            Ok(Self::new(num, den))
+        } else {
+            Ok(Self::from_integer(int(s)?))
+        }
+    }
+}
+
+impl From<BigInt> for BigRational {
+    fn from(i: BigInt) -> Self {
+        Self::from_integer(i)
+    }
+}
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigRational {
+                fn from(i: $t) -> Self {
+                    Self::from_integer(BigInt::from(i))
+                }
+            }
+        )*
+    }
+}
+
+impl_from_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Floats are converted exactly, since every finite float is a rational.
+impl TryFrom<f64> for BigRational {
+    type Error = TryFromFloatError;
+
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        if !f.is_finite() {
+            return Err(TryFromFloatError(()));
+        }
+
+        let bits = f.to_bits();
+        let exp = (bits >> 52 & 0x7ff) as i64;
+        let frac = bits & ((1 << 52) - 1);
+
+        // subnormals have no implicit leading bit and share the exponent of the smallest normal
+        let (mantissa, exp) = if exp == 0 {
+            (frac, -1074)
+        } else {
+            (frac | 1 << 52, exp - 1075)
+        };
+
+        let mut num = BigInt::from(mantissa);
+        num.signed = f.is_sign_negative() && mantissa != 0;
+
+        Ok(if exp >= 0 {
+            Self::from_integer(num << exp as usize)
+        } else {
+            Self::new(num, BigInt::one() << (-exp) as usize)
+        })
+    }
+}
+
+impl From<&BigRational> for f64 {
+    fn from(r: &BigRational) -> Self {
+        r.to_f64()
+    }
+}
+
+impl From<BigRational> for f64 {
+    fn from(r: BigRational) -> Self {
+        r.to_f64()
+    }
+}
+
+impl Neg for BigRational {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            num: negate(self.num),
+            den: self.den,
+        }
+    }
+}
+
+impl Neg for &BigRational {
+    type Output = BigRational;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+macro_rules! impl_rational_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl $op<&BigRational> for &BigRational {
+            type Output = BigRational;
+
+            fn $op_fn(self, other: &BigRational) -> Self::Output {
+                let ($a, $b) = (self, other);
+                $body
+            }
+        }
+
+        impl $op for BigRational {
+            type Output = Self;
+
+            fn $op_fn(self, other: Self) -> Self::Output {
+                (&self).$op_fn(&other)
+            }
+        }
+
+        impl $op<&BigRational> for BigRational {
+            type Output = Self;
+
+            fn $op_fn(self, other: &Self) -> Self::Output {
+                (&self).$op_fn(other)
+            }
+        }
+
+        impl $op<BigRational> for &BigRational {
+            type Output = BigRational;
+
+            fn $op_fn(self, other: BigRational) -> Self::Output {
+                self.$op_fn(&other)
+            }
+        }
+
+        impl $assign for BigRational {
+            fn $assign_fn(&mut self, other: Self) {
+                *self = (&*self).$op_fn(&other);
+            }
+        }
+
+        impl $assign<&BigRational> for BigRational {
+            fn $assign_fn(&mut self, other: &Self) {
+                *self = (&*self).$op_fn(other);
+            }
+        }
+    };
+}
+
+impl_rational_op!(Add, add, AddAssign, add_assign, |a, b| {
+    BigRational::new(&a.num * &b.den + &b.num * &a.den, &a.den * &b.den)
+});
+
+impl_rational_op!(Sub, sub, SubAssign, sub_assign, |a, b| {
+    BigRational::new(&a.num * &b.den - &b.num * &a.den, &a.den * &b.den)
+});
+
+impl_rational_op!(Mul, mul, MulAssign, mul_assign, |a, b| {
+    BigRational::new(&a.num * &b.num, &a.den * &b.den)
+});
+
+impl_rational_op!(Div, div, DivAssign, div_assign, |a, b| {
+    assert!(b.num != 0, "attempt to divide by zero");
+    BigRational::new(&a.num * &b.den, &a.den * &b.num)
+});
+
+// The remainder has the sign of the dividend, as with `BigInt`.
+impl_rational_op!(Rem, rem, RemAssign, rem_assign, |a, b| {
+    a - b * BigRational::from_integer((a / b).trunc())
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn r(num: i64, den: i64) -> BigRational {
+        BigRational::new(BigInt::from(num), BigInt::from(den))
+    }
+
+    #[test]
+    fn normalized() {
+        assert_eq!(r(6, -8), r(-3, 4));
+        assert_eq!(r(6, -8).denom(), &BigInt::from(4));
+        assert_eq!(r(0, -5), BigRational::zero());
+        assert_eq!(r(0, -5).denom(), &BigInt::one());
+    }
+
+    #[test]
+    #[should_panic = "attempt to create a rational with a zero denominator"]
+    fn zero_denominator() {
+        r(1, 0);
+    }
+
+    macro_rules! test_op {
+        ($name:ident: $a:expr, $op:tt, $b:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!($a $op $b, $e);
+            }
+        };
+    }
+
+    test_op!(add: r(1, 2), +, r(1, 3), r(5, 6));
+
+    test_op!(add_to_integer: r(3, 4), +, r(5, 4), r(2, 1));
+
+    test_op!(sub: r(1, 2), -, r(3, 4), r(-1, 4));
+
+    test_op!(mul: r(-2, 3), *, r(9, 4), r(-3, 2));
+
+    test_op!(div: r(1, 2), /, r(-1, 4), r(-2, 1));
+
+    test_op!(rem: r(7, 2), %, r(1, 1), r(1, 2));
+
+    test_op!(rem_negative: r(-7, 2), %, r(3, 4), r(-1, 2));
+
+    #[test]
+    #[should_panic = "attempt to divide by zero"]
+    fn div_zero() {
+        let _ = r(1, 2) / BigRational::zero();
+    }
+
+    #[test]
+    fn cmp() {
+        assert!(r(1, 3) < r(1, 2));
+        assert!(r(-1, 2) < r(-1, 3));
+        assert!(r(7, 7) == BigRational::one());
+        assert_eq!(r(2, 3).max(r(3, 5)), r(2, 3));
+    }
+
+    #[test]
+    fn rounding() {
+        for (n, d, floor, ceil, trunc, round) in [
+            (7, 2, 3, 4, 3, 4),
+            (-7, 2, -4, -3, -3, -4),
+            (5, 3, 1, 2, 1, 2),
+            (-5, 3, -2, -1, -1, -2),
+            (4, 3, 1, 2, 1, 1),
+            (-4, 3, -2, -1, -1, -1),
+            (6, 3, 2, 2, 2, 2),
+        ] {
+            let x = r(n, d);
+            assert_eq!(x.floor(), floor, "floor {x}");
+            assert_eq!(x.ceil(), ceil, "ceil {x}");
+            assert_eq!(x.trunc(), trunc, "trunc {x}");
+            assert_eq!(x.round(), round, "round {x}");
+        }
+    }
+
+    #[test]
+    fn pow() {
+        assert_eq!(r(-2, 3).pow(3), r(-8, 27));
+        assert_eq!(r(-2, 3).pow(-2), r(9, 4));
+        assert_eq!(r(5, 7).pow(0), BigRational::one());
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!("3/4".parse::<BigRational>().unwrap(), r(3, 4));
+        assert_eq!("-6/8".parse::<BigRational>().unwrap(), r(-3, 4));
+        assert_eq!("0.125".parse::<BigRational>().unwrap(), r(1, 8));
+        assert_eq!("-1.5".parse::<BigRational>().unwrap(), r(-3, 2));
+        assert_eq!(".5".parse::<BigRational>().unwrap(), r(1, 2));
+        assert_eq!("2.".parse::<BigRational>().unwrap(), r(2, 1));
+        assert_eq!("-0.0".parse::<BigRational>().unwrap(), BigRational::zero());
+        assert_eq!("42".parse::<BigRational>().unwrap(), r(42, 1));
+
+        for s in [
+            "", "1/0", "1/", ".", "-.", "1.2.3", "1/2/3", "0x1.8", "1.-5",
+        ] {
+            assert!(s.parse::<BigRational>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(r(-3, 4).to_string(), "-3/4");
+        assert_eq!(r(8, 4).to_string(), "2");
+        assert_eq!(format!("{:.3}", r(1, 3)), "0.333");
+        assert_eq!(format!("{:.3}", r(2, 3)), "0.667");
+        assert_eq!(format!("{:.2}", r(-1, 8)), "-0.13");
+        assert_eq!(format!("{:.0}", r(5, 2)), "3");
+        assert_eq!(format!("{:.4}", r(-1, 100_000)), "0.0000");
+        assert_eq!(r(22, 7).to_decimal_string(10), "3.1428571429");
+    }
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(BigRational::try_from(0.75).unwrap(), r(3, 4));
+        assert_eq!(BigRational::try_from(-2.0).unwrap(), r(-2, 1));
+        assert_eq!(BigRational::try_from(-0.0).unwrap(), BigRational::zero());
+        assert_eq!(
+            BigRational::try_from(f64::MIN_POSITIVE / 4.0).unwrap(),
+            BigRational::new(BigInt::one(), BigInt::one() << 1024u16)
+        );
+        assert!(BigRational::try_from(f64::NAN).is_err());
+        assert!(BigRational::try_from(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn to_f64() {
+        // division of small integers is correctly rounded, so it is a reference
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+        for _ in 0..2000 {
+            let (a, b) = (
+                (next() >> 11) as i64,
+                (next() >> (11 + next() % 50)) as i64 + 1,
+            );
+            assert_eq!(r(a, b).to_f64(), a as f64 / b as f64, "{a}/{b}");
+            assert_eq!(
+                r(-b, a.max(1)).to_f64(),
+                -b as f64 / a.max(1) as f64,
+                "-{b}/{a}"
+            );
+        }
+    }
+
+    #[test]
+    fn f64_round_trip() {
+        for f in [
+            0.1,
+            -1e300,
+            1e-300,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::MIN_POSITIVE / 3.0,
+            5e-324,
+            std::f64::consts::PI,
+        ] {
+            assert_eq!(BigRational::try_from(f).unwrap().to_f64(), f, "{f}");
+        }
+    }
+
+    #[test]
+    fn to_f64_limits() {
+        let two = BigInt::from(2);
+        assert_eq!(
+            BigRational::from_integer(two.pow(1024u16)).to_f64(),
+            f64::INFINITY
+        );
+        assert_eq!(
+            BigRational::new(BigInt::one(), two.pow(1076u16)).to_f64(),
+            0.0
+        );
+        assert_eq!(
+            BigRational::new(BigInt::from(3), two.pow(1076u16)).to_f64(),
+            5e-324
+        );
+        assert_eq!(
+            BigRational::new(BigInt::one(), two.pow(1075u16)).to_f64(),
+            0.0
+        );
+        assert_eq!(r(1, 3).to_f64(), 1.0 / 3.0);
+    }
+}
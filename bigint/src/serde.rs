@@ -0,0 +1,72 @@
+//! Implementations of the `serde` traits. A `BigInt` is serialized as a decimal string, so it
+//! survives formats whose numbers are limited to 64 bits or to floats, and is deserialized from a
+//! string in any of the forms accepted by `FromStr`.
+
+use std::fmt::{self, Formatter};
+
+use serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+use serde::{Serialize, Serializer};
+
+use crate::BigInt;
+
+impl Serialize for BigInt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+struct BigIntVisitor;
+
+impl<'de> Visitor<'de> for BigIntVisitor {
+    type Value = BigInt;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "an integer string")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse()
+            .map_err(|_| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+impl<'de> Deserialize<'de> for BigInt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(BigIntVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_test::{assert_de_tokens, assert_de_tokens_error, assert_tokens, Token};
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        assert_tokens(&BigInt::zero(), &[Token::Str("0")]);
+        assert_tokens(&BigInt::from(-12345), &[Token::Str("-12345")]);
+
+        assert_tokens(
+            &(BigInt::one() << 200u8),
+            &[Token::Str(
+                "1606938044258990275541962092341162602522202993782792835301376",
+            )],
+        );
+    }
+
+    #[test]
+    fn deserialize() {
+        assert_de_tokens(&BigInt::from(255), &[Token::String("0xff")]);
+        assert_de_tokens(&BigInt::from(-7), &[Token::BorrowedStr("-7")]);
+
+        assert_de_tokens_error::<BigInt>(
+            &[Token::Str("12a")],
+            "invalid value: string \"12a\", expected an integer string",
+        );
+        assert_de_tokens_error::<BigInt>(
+            &[Token::I32(5)],
+            "invalid type: integer `5`, expected an integer string",
+        );
+    }
+}
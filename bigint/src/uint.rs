@@ -0,0 +1,184 @@
+//! Fixed-width unsigned integers, stored inline without any heap allocation.
+//!
+//! Unlike `BigInt`, the arithmetic operators panic when a result does not fit in the type. The
+//! `wrapping_*`, `checked_*` and `overflowing_*` methods behave like those on the primitive
+//! integer types.
+
+pub mod arithmetic;
+pub mod cmp;
+pub mod convert;
+pub mod fmt;
+pub mod logical;
+
+use crate::Base;
+
+/// An unsigned integer made up of `LIMBS` machine words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Uint<const LIMBS: usize> {
+    pub(crate) data: [Base; LIMBS],
+}
+
+pub type U128 = Uint<{ 128 / Base::BITS as usize }>;
+pub type U256 = Uint<{ 256 / Base::BITS as usize }>;
+pub type U512 = Uint<{ 512 / Base::BITS as usize }>;
+pub type U1024 = Uint<{ 1024 / Base::BITS as usize }>;
+pub type U2048 = Uint<{ 2048 / Base::BITS as usize }>;
+pub type U4096 = Uint<{ 4096 / Base::BITS as usize }>;
+
+impl<const LIMBS: usize> Uint<LIMBS> {
+    /// The size of the integer type in bits.
+    pub const BITS: u32 = LIMBS as u32 * Base::BITS;
+
+    /// The value `0`.
+    pub const ZERO: Self = Self { data: [0; LIMBS] };
+
+    /// The value `1`.
+    pub const ONE: Self = {
+        let mut data = [0; LIMBS];
+        data[0] = 1;
+        Self { data }
+    };
+
+    /// The largest value which can be represented by this type, `2^BITS - 1`.
+    pub const MAX: Self = Self {
+        data: [Base::MAX; LIMBS],
+    };
+
+    /// Creates a `Uint` with the value of `0`.
+    pub const fn zero() -> Self {
+        Self::ZERO
+    }
+
+    /// Creates a `Uint` with the value of `1`.
+    pub const fn one() -> Self {
+        Self::ONE
+    }
+
+    // The limbs without any leading zeros, leaving at least one.
+    pub(crate) fn trimmed(&self) -> &[Base] {
+        let len = LIMBS - self.data.iter().rev().take_while(|&&e| e == 0).count();
+        &self.data[..len.max(1)]
+    }
+
+    // Creates a `Uint` from little-endian limbs, or `None` if they do not fit.
+    pub(crate) fn from_limbs(limbs: &[Base]) -> Option<Self> {
+        if limbs.iter().skip(LIMBS).any(|&e| e != 0) {
+            return None;
+        }
+
+        let mut data = [0; LIMBS];
+        let len = limbs.len().min(LIMBS);
+        data[..len].copy_from_slice(&limbs[..len]);
+        Some(Self { data })
+    }
+
+    /// Returns true if `self` is `0`.
+    pub fn is_zero(&self) -> bool {
+        self.data.iter().all(|&e| e == 0)
+    }
+
+    /// Returns true if `self` is even.
+    pub fn is_even(&self) -> bool {
+        self.data[0] & 1 == 0
+    }
+
+    /// Returns true if `self` is odd.
+    pub fn is_odd(&self) -> bool {
+        self.data[0] & 1 == 1
+    }
+
+    /// Returns the number of bits representing the number, ignoring leading zeros.
+    pub fn bits(&self) -> usize {
+        Self::BITS as usize - self.leading_zeros()
+    }
+
+    /// Returns the number of ones in the binary representation of the number.
+    pub fn count_ones(&self) -> usize {
+        self.data.iter().map(|e| e.count_ones() as usize).sum()
+    }
+
+    /// Returns the number of zeros in the binary representation of the number, ignoring leading zeros.
+    pub fn count_zeros(&self) -> usize {
+        self.bits() - self.count_ones()
+    }
+
+    /// Returns the number of leading zeros in the binary representation of the number.
+    pub fn leading_zeros(&self) -> usize {
+        let mut x = 0usize;
+        for i in self.data.iter().rev() {
+            let b = i.leading_zeros();
+            x += b as usize;
+            if b != Base::BITS {
+                break;
+            }
+        }
+        x
+    }
+
+    /// Returns the number of trailing ones in the binary representation of the number.
+    pub fn trailing_ones(&self) -> usize {
+        let mut x = 0usize;
+        for i in self.data.iter() {
+            let b = i.trailing_ones();
+            x += b as usize;
+            if b != Base::BITS {
+                break;
+            }
+        }
+        x
+    }
+
+    /// Returns the number of trailing zeros in the binary representation of the number.
+    pub fn trailing_zeros(&self) -> usize {
+        let mut x = 0usize;
+        for i in self.data.iter() {
+            let b = i.trailing_zeros();
+            x += b as usize;
+            if b != Base::BITS {
+                break;
+            }
+        }
+        x
+    }
+}
+
+impl<const LIMBS: usize> Default for Uint<LIMBS> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sizes() {
+        assert_eq!(U128::BITS, 128);
+        assert_eq!(U256::BITS, 256);
+        assert_eq!(U512::BITS, 512);
+        assert_eq!(U4096::BITS, 4096);
+        assert_eq!(std::mem::size_of::<U4096>(), 512);
+    }
+
+    #[test]
+    fn bit_counts() {
+        let x = U256::from(0b1011_0000u8);
+        assert_eq!(x.bits(), 8);
+        assert_eq!(x.count_ones(), 3);
+        assert_eq!(x.count_zeros(), 5);
+        assert_eq!(x.leading_zeros(), 248);
+        assert_eq!(x.trailing_zeros(), 4);
+        assert_eq!(U256::MAX.trailing_ones(), 256);
+        assert_eq!(U256::ZERO.leading_zeros(), 256);
+        assert_eq!(U256::ZERO.bits(), 0);
+    }
+
+    #[test]
+    fn parity() {
+        assert!(U256::ZERO.is_even());
+        assert!(U256::ZERO.is_zero());
+        assert!(U256::ONE.is_odd());
+        assert!(!U256::MAX.is_zero());
+    }
+}
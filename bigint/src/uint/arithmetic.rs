@@ -0,0 +1,341 @@
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Rem, RemAssign, Sub, SubAssign};
+
+use crate::arithmetic::div::div_rem_limbs;
+use crate::uint::Uint;
+use crate::Base;
+
+impl<const LIMBS: usize> Uint<LIMBS> {
+    /// Calculates `self + other`, returning the wrapped result and whether it overflowed.
+    pub fn overflowing_add(mut self, other: Self) -> (Self, bool) {
+        let mut carry = false;
+        for (a, &b) in self.data.iter_mut().zip(other.data.iter()) {
+            (*a, carry) = a.carrying_add(b, carry);
+        }
+        (self, carry)
+    }
+
+    /// Calculates `self - other`, returning the wrapped result and whether it overflowed.
+    pub fn overflowing_sub(mut self, other: Self) -> (Self, bool) {
+        let mut borrow = false;
+        for (a, &b) in self.data.iter_mut().zip(other.data.iter()) {
+            (*a, borrow) = a.borrowing_sub(b, borrow);
+        }
+        (self, borrow)
+    }
+
+    /// Calculates `self * other`, returning the wrapped result and whether it overflowed.
+    pub fn overflowing_mul(self, other: Self) -> (Self, bool) {
+        let mut out = [0; LIMBS];
+        let mut overflow = false;
+        let (mut lo, mut hi, mut carry, mut o);
+
+        for (i, &b) in other.data.iter().enumerate() {
+            if b == 0 {
+                continue;
+            }
+
+            carry = 0;
+            for (j, &a) in self.data.iter().enumerate() {
+                if i + j < LIMBS {
+                    (lo, hi) = a.carrying_mul(b, carry);
+                    (out[i + j], o) = lo.overflowing_add(out[i + j]);
+                    carry = hi + o as Base;
+                } else {
+                    overflow |= a != 0;
+                }
+            }
+            overflow |= carry != 0;
+        }
+
+        (Self { data: out }, overflow)
+    }
+
+    /// Raises `self` to the power of `exp`, returning the wrapped result and whether it overflowed.
+    pub fn overflowing_pow(self, mut exp: u32) -> (Self, bool) {
+        let (mut base, mut acc) = (self, Self::ONE);
+        let mut overflow = false;
+        let mut o;
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                (acc, o) = acc.overflowing_mul(base);
+                overflow |= o;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                (base, o) = base.overflowing_mul(base);
+                overflow |= o;
+            }
+        }
+
+        (acc, overflow)
+    }
+
+    /// Calculates `self + other`, wrapping around at the boundary of the type.
+    pub fn wrapping_add(self, other: Self) -> Self {
+        self.overflowing_add(other).0
+    }
+
+    /// Calculates `self - other`, wrapping around at the boundary of the type.
+    pub fn wrapping_sub(self, other: Self) -> Self {
+        self.overflowing_sub(other).0
+    }
+
+    /// Calculates `self * other`, wrapping around at the boundary of the type.
+    pub fn wrapping_mul(self, other: Self) -> Self {
+        self.overflowing_mul(other).0
+    }
+
+    /// Raises `self` to the power of `exp`, wrapping around at the boundary of the type.
+    pub fn wrapping_pow(self, exp: u32) -> Self {
+        self.overflowing_pow(exp).0
+    }
+
+    /// Calculates `-self`, wrapping around at the boundary of the type.
+    pub fn wrapping_neg(self) -> Self {
+        Self::ZERO.wrapping_sub(self)
+    }
+
+    /// Calculates `self + other`, or `None` if it overflows.
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        match self.overflowing_add(other) {
+            (val, false) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Calculates `self - other`, or `None` if it overflows.
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.overflowing_sub(other) {
+            (val, false) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Calculates `self * other`, or `None` if it overflows.
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        match self.overflowing_mul(other) {
+            (val, false) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Raises `self` to the power of `exp`, or `None` if it overflows.
+    pub fn checked_pow(self, exp: u32) -> Option<Self> {
+        match self.overflowing_pow(exp) {
+            (val, false) => Some(val),
+            _ => None,
+        }
+    }
+
+    /// Calculates `self / other`, or `None` if `other` is `0`.
+    pub fn checked_div(self, other: Self) -> Option<Self> {
+        (!other.is_zero()).then(|| self.div_rem(other).0)
+    }
+
+    /// Calculates `self % other`, or `None` if `other` is `0`.
+    pub fn checked_rem(self, other: Self) -> Option<Self> {
+        (!other.is_zero()).then(|| self.div_rem(other).1)
+    }
+
+    /// Raises `self` to the power of `exp`. Panics if the result overflows.
+    pub fn pow(self, exp: u32) -> Self {
+        self.checked_pow(exp).expect("attempt to raise to a power with overflow")
+    }
+
+    /// Calculates the quotient and remainder of `self / other`. Panics if `other` is `0`.
+    pub fn div_rem(self, other: Self) -> (Self, Self) {
+        assert!(!other.is_zero(), "attempt to divide by zero");
+
+        let (quot, rem) = div_rem_limbs(self.trimmed(), other.trimmed());
+        (
+            Self::from_limbs(&quot).unwrap(),
+            Self::from_limbs(&rem).unwrap(),
+        )
+    }
+}
+
+macro_rules! impl_uint_op {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl<const LIMBS: usize> $op for Uint<LIMBS> {
+            type Output = Self;
+
+            fn $op_fn(self, other: Self) -> Self::Output {
+                let ($a, $b) = (self, other);
+                $body
+            }
+        }
+
+        impl<const LIMBS: usize> $op<&Uint<LIMBS>> for Uint<LIMBS> {
+            type Output = Self;
+
+            fn $op_fn(self, other: &Self) -> Self::Output {
+                self.$op_fn(*other)
+            }
+        }
+
+        impl<const LIMBS: usize> $op<Uint<LIMBS>> for &Uint<LIMBS> {
+            type Output = Uint<LIMBS>;
+
+            fn $op_fn(self, other: Uint<LIMBS>) -> Self::Output {
+                (*self).$op_fn(other)
+            }
+        }
+
+        impl<const LIMBS: usize> $op<&Uint<LIMBS>> for &Uint<LIMBS> {
+            type Output = Uint<LIMBS>;
+
+            fn $op_fn(self, other: &Uint<LIMBS>) -> Self::Output {
+                (*self).$op_fn(*other)
+            }
+        }
+
+        impl<const LIMBS: usize> $assign for Uint<LIMBS> {
+            fn $assign_fn(&mut self, other: Self) {
+                *self = (*self).$op_fn(other);
+            }
+        }
+
+        impl<const LIMBS: usize> $assign<&Uint<LIMBS>> for Uint<LIMBS> {
+            fn $assign_fn(&mut self, other: &Self) {
+                *self = (*self).$op_fn(*other);
+            }
+        }
+    };
+}
+
+impl_uint_op!(Add, add, AddAssign, add_assign, |a, b| {
+    a.checked_add(b).expect("attempt to add with overflow")
+});
+
+impl_uint_op!(Sub, sub, SubAssign, sub_assign, |a, b| {
+    a.checked_sub(b).expect("attempt to subtract with overflow")
+});
+
+impl_uint_op!(Mul, mul, MulAssign, mul_assign, |a, b| {
+    a.checked_mul(b).expect("attempt to multiply with overflow")
+});
+
+impl_uint_op!(Div, div, DivAssign, div_assign, |a, b| a.div_rem(b).0);
+
+impl_uint_op!(Rem, rem, RemAssign, rem_assign, |a, b| a.div_rem(b).1);
+
+#[cfg(test)]
+mod tests {
+    use crate::uint::{U128, U256};
+
+    // xorshift, so the operands are reproducible without a dependency on an rng
+    fn values(seed: &mut u64) -> (u128, u128) {
+        let mut next = || {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 7;
+            *seed ^= *seed << 17;
+            *seed
+        };
+
+        // vary the sizes so that small values and the carries between limbs are both covered
+        let a = ((next() as u128) << 64 | next() as u128) >> (next() % 128);
+        let b = ((next() as u128) << 64 | next() as u128) >> (next() % 128);
+        (a, b)
+    }
+
+    macro_rules! test_against_u128 {
+        ($name:ident: |$a:ident, $b:ident| $uint:expr, $prim:expr) => {
+            #[test]
+            fn $name() {
+                let mut seed = 0x2545_f491_4f6c_dd1d;
+                for _ in 0..1000 {
+                    let (x, y) = values(&mut seed);
+                    let ($a, $b) = (U128::from(x), U128::from(y));
+                    let uint = $uint;
+                    let ($a, $b) = (x, y);
+                    assert_eq!(uint, $prim, "{x} {y}");
+                }
+            }
+        };
+    }
+
+    test_against_u128!(overflowing_add: |a, b| {
+        let (v, o) = a.overflowing_add(b);
+        (u128::try_from(v).unwrap(), o)
+    }, a.overflowing_add(b));
+
+    test_against_u128!(overflowing_sub: |a, b| {
+        let (v, o) = a.overflowing_sub(b);
+        (u128::try_from(v).unwrap(), o)
+    }, a.overflowing_sub(b));
+
+    test_against_u128!(overflowing_mul: |a, b| {
+        let (v, o) = a.overflowing_mul(b);
+        (u128::try_from(v).unwrap(), o)
+    }, a.overflowing_mul(b));
+
+    test_against_u128!(overflowing_pow: |a, b| {
+        let (v, o) = a.overflowing_pow(u32::try_from(b & U128::from(7u8)).unwrap());
+        (u128::try_from(v).unwrap(), o)
+    }, a.overflowing_pow((b % 8) as u32));
+
+    test_against_u128!(checked_div: |a, b| {
+        a.checked_div(b).map(|e| u128::try_from(e).unwrap())
+    }, a.checked_div(b));
+
+    test_against_u128!(checked_rem: |a, b| {
+        a.checked_rem(b).map(|e| u128::try_from(e).unwrap())
+    }, a.checked_rem(b));
+
+    test_against_u128!(wrapping_neg: |a, _b| {
+        u128::try_from(a.wrapping_neg()).unwrap()
+    }, a.wrapping_neg());
+
+    #[test]
+    fn operators() {
+        let a = U256::from(u128::MAX);
+        let b = U256::from(0xdead_beef_u32);
+
+        assert_eq!(a + b - b, a);
+        assert_eq!((a * b) / b, a);
+        assert_eq!((a * b + U256::from(7u8)) % b, U256::from(7u8));
+        assert_eq!(&a + &b, a + b);
+        assert_eq!(&a - b, a - b);
+        assert_eq!(a * &b, a * b);
+
+        let mut c = a;
+        c *= b;
+        c /= b;
+        c += U256::ONE;
+        c -= &U256::ONE;
+        assert_eq!(c, a);
+    }
+
+    #[test]
+    fn pow() {
+        assert_eq!(U256::from(2u8).pow(255), U256::ONE << 255u32);
+        assert_eq!(U256::from(2u8).checked_pow(256), None);
+        assert_eq!(U256::from(2u8).wrapping_pow(256), U256::ZERO);
+        assert_eq!(U256::ZERO.pow(0), U256::ONE);
+    }
+
+    #[test]
+    #[should_panic = "attempt to add with overflow"]
+    fn add_overflow() {
+        let _ = U256::MAX + U256::ONE;
+    }
+
+    #[test]
+    #[should_panic = "attempt to subtract with overflow"]
+    fn sub_overflow() {
+        let _ = U256::ZERO - U256::ONE;
+    }
+
+    #[test]
+    #[should_panic = "attempt to multiply with overflow"]
+    fn mul_overflow() {
+        let _ = (U256::ONE << 128u32) * (U256::ONE << 128u32);
+    }
+
+    #[test]
+    #[should_panic = "attempt to divide by zero"]
+    fn div_zero() {
+        let _ = U256::ONE / U256::ZERO;
+    }
+}
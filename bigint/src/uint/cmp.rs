@@ -0,0 +1,70 @@
+use std::cmp::Ordering;
+
+use crate::uint::Uint;
+
+impl<const LIMBS: usize> Ord for Uint<LIMBS> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.data.iter().rev().cmp(other.data.iter().rev())
+    }
+}
+
+impl<const LIMBS: usize> PartialOrd for Uint<LIMBS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+macro_rules! impl_primitive_cmp {
+    ($($t:ty),*) => {
+        $(
+            impl<const LIMBS: usize> PartialEq<$t> for Uint<LIMBS> {
+                fn eq(&self, other: &$t) -> bool {
+                    Uint::try_from_le_bytes(&other.to_le_bytes()).map_or(false, |e| *self == e)
+                }
+            }
+
+            impl<const LIMBS: usize> PartialOrd<$t> for Uint<LIMBS> {
+                fn partial_cmp(&self, other: &$t) -> Option<Ordering> {
+                    // a primitive that does not fit is larger than anything in the type
+                    let other = Uint::try_from_le_bytes(&other.to_le_bytes());
+                    Some(other.map_or(Ordering::Less, |e| self.cmp(&e)))
+                }
+            }
+        )*
+    }
+}
+
+impl_primitive_cmp!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uint::U256;
+
+    macro_rules! test_cmp {
+        ($name:ident: $a:expr, $b:expr, $e:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!($a.cmp(&$b), $e);
+                assert_eq!($b.cmp(&$a), $e.reverse());
+            }
+        };
+    }
+
+    test_cmp!(equal: U256::from(5u8), U256::from(5u8), Ordering::Equal);
+
+    test_cmp!(less: U256::from(4u8), U256::from(5u8), Ordering::Less);
+
+    test_cmp!(high_limb: U256::ONE << 200u32, U256::from(u128::MAX), Ordering::Greater);
+
+    test_cmp!(max: U256::MAX, U256::MAX - U256::ONE, Ordering::Greater);
+
+    #[test]
+    fn primitive() {
+        assert_eq!(U256::from(42u8), 42u8);
+        assert!(U256::from(42u8) < 43u64);
+        assert!(U256::MAX > u128::MAX);
+        assert!(Uint::<1>::MAX < u128::MAX);
+        assert_ne!(Uint::<1>::MAX, u128::MAX);
+    }
+}
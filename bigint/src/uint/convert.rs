@@ -0,0 +1,217 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    mem,
+    str::FromStr,
+};
+
+use crate::uint::Uint;
+use crate::{Base, BigInt};
+
+#[derive(Debug)]
+/// An error generated when trying to parse a string into a `Uint`.
+pub struct ParseUintError(());
+
+impl Error for ParseUintError {}
+
+impl Display for ParseUintError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid integer value")
+    }
+}
+
+#[derive(Debug)]
+/// An error generated when trying to convert between a `Uint` and another integer type.
+pub struct TryFromUintError(());
+
+impl Error for TryFromUintError {}
+
+impl Display for TryFromUintError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "integer value out of bounds for destination type")
+    }
+}
+
+impl<const LIMBS: usize> Uint<LIMBS> {
+    /// Converts `self` into bytes in a big-endian order, including any leading zeros.
+    pub fn to_be_bytes(&self) -> Vec<u8> {
+        self.to_le_bytes().into_iter().rev().collect()
+    }
+
+    /// Converts `self` into bytes in a little-endian order, including any trailing zeros.
+    pub fn to_le_bytes(&self) -> Vec<u8> {
+        self.data.iter().flat_map(|e| e.to_le_bytes()).collect()
+    }
+
+    /// Converts big-endian bytes into a `Uint`. Panics if the value does not fit.
+    pub fn from_be_bytes(bytes: &[u8]) -> Self {
+        let mut bytes = bytes.to_vec();
+        bytes.reverse();
+        Self::from_le_bytes(&bytes)
+    }
+
+    /// Converts little-endian bytes into a `Uint`. Panics if the value does not fit.
+    pub fn from_le_bytes(bytes: &[u8]) -> Self {
+        Self::try_from_le_bytes(bytes).expect("integer value out of bounds for destination type")
+    }
+
+    // Converts little-endian bytes into a `Uint`, or `None` if the value does not fit.
+    pub(crate) fn try_from_le_bytes(bytes: &[u8]) -> Option<Self> {
+        const BYTES: usize = Base::BITS as usize / 8;
+
+        let limbs = bytes
+            .chunks(BYTES)
+            .map(|e| {
+                let mut limb = [0; BYTES];
+                limb[..e.len()].copy_from_slice(e);
+                Base::from_le_bytes(limb)
+            })
+            .collect::<Vec<_>>();
+
+        Self::from_limbs(&limbs)
+    }
+
+    /// Converts a string in a given base to a `Uint`. Panics if `radix` is not in `2..=36`.
+    pub fn from_str_radix(s: &str, radix: u8) -> Result<Self, ParseUintError> {
+        match BigInt::from_str_radix(s, radix) {
+            Ok(i) => Self::try_from(i).map_err(|_| ParseUintError(())),
+            Err(_) => Err(ParseUintError(())),
+        }
+    }
+}
+
+impl<const LIMBS: usize> FromStr for Uint<LIMBS> {
+    type Err = ParseUintError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match BigInt::from_str(s) {
+            Ok(i) => Self::try_from(i).map_err(|_| ParseUintError(())),
+            Err(_) => Err(ParseUintError(())),
+        }
+    }
+}
+
+impl<const LIMBS: usize> From<Uint<LIMBS>> for BigInt {
+    fn from(num: Uint<LIMBS>) -> Self {
+        BigInt::from_limbs(num.data.to_vec())
+    }
+}
+
+impl<const LIMBS: usize> From<&Uint<LIMBS>> for BigInt {
+    fn from(num: &Uint<LIMBS>) -> Self {
+        BigInt::from(*num)
+    }
+}
+
+impl<const LIMBS: usize> TryFrom<BigInt> for Uint<LIMBS> {
+    type Error = TryFromUintError;
+
+    fn try_from(i: BigInt) -> Result<Self, Self::Error> {
+        Uint::try_from(&i)
+    }
+}
+
+impl<const LIMBS: usize> TryFrom<&BigInt> for Uint<LIMBS> {
+    type Error = TryFromUintError;
+
+    fn try_from(i: &BigInt) -> Result<Self, Self::Error> {
+        if i.is_negative() {
+            return Err(TryFromUintError(()));
+        }
+        Uint::from_limbs(&i.data).ok_or(TryFromUintError(()))
+    }
+}
+
+macro_rules! impl_from_uint {
+    ($($t:ty),*) => {
+        $(
+            impl<const LIMBS: usize> From<$t> for Uint<LIMBS> {
+                /// Panics if the value does not fit, which can only happen when the `Uint` is
+                /// narrower than the primitive type.
+                fn from(num: $t) -> Self {
+                    Uint::from_le_bytes(&num.to_le_bytes())
+                }
+            }
+
+            impl<const LIMBS: usize> TryFrom<Uint<LIMBS>> for $t {
+                type Error = TryFromUintError;
+
+                fn try_from(i: Uint<LIMBS>) -> Result<Self, Self::Error> {
+                    <$t>::try_from(&i)
+                }
+            }
+
+            impl<const LIMBS: usize> TryFrom<&Uint<LIMBS>> for $t {
+                type Error = TryFromUintError;
+
+                fn try_from(i: &Uint<LIMBS>) -> Result<Self, Self::Error> {
+                    const BYTES: usize = mem::size_of::<$t>();
+
+                    if i.bits() > <$t>::BITS as usize {
+                        return Err(TryFromUintError(()));
+                    }
+
+                    let mut bytes = i.to_le_bytes();
+                    bytes.resize(bytes.len().max(BYTES), 0);
+                    Ok(<$t>::from_le_bytes(bytes[..BYTES].try_into().unwrap()))
+                }
+            }
+        )*
+    }
+}
+
+impl_from_uint!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uint::{U128, U256};
+
+    #[test]
+    fn primitive() {
+        assert_eq!(u128::try_from(U256::from(u128::MAX)).unwrap(), u128::MAX);
+        assert_eq!(u8::try_from(U256::from(255u8)).unwrap(), 255);
+        assert!(u8::try_from(U256::from(256u16)).is_err());
+        assert!(u128::try_from(U256::ONE << 128u32).is_err());
+        assert_eq!(u128::try_from(Uint::<1>::MAX).unwrap(), Base::MAX as u128);
+    }
+
+    #[test]
+    #[should_panic = "integer value out of bounds for destination type"]
+    fn primitive_too_wide() {
+        let _ = Uint::<1>::from(u128::MAX);
+    }
+
+    #[test]
+    fn bytes() {
+        let x = U128::from(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10_u128);
+        assert_eq!(x.to_be_bytes(), 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10_u128.to_be_bytes());
+        assert_eq!(x.to_le_bytes(), 0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10_u128.to_le_bytes());
+        assert_eq!(U128::from_be_bytes(&x.to_be_bytes()), x);
+        assert_eq!(U128::from_le_bytes(&x.to_le_bytes()), x);
+        assert_eq!(U256::from_be_bytes(&[0, 0, 1, 2]), U256::from(0x0102u16));
+        assert_eq!(U256::ZERO.to_be_bytes(), [0; 32]);
+    }
+
+    #[test]
+    fn bigint() {
+        let x = (U256::MAX >> 3u32) - U256::from(12345u16);
+        let i = BigInt::from(x);
+        assert_eq!(i, (BigInt::one() << 253u32) - 12346u16);
+        assert_eq!(U256::try_from(&i).unwrap(), x);
+
+        assert!(U256::try_from(BigInt::one() << 256u32).is_err());
+        assert!(U256::try_from(BigInt::from(-1)).is_err());
+        assert_eq!(U256::try_from(BigInt::zero()).unwrap(), U256::ZERO);
+    }
+
+    #[test]
+    fn from_str() {
+        assert_eq!(U256::from_str("12345").unwrap(), U256::from(12345u16));
+        assert_eq!(U256::from_str("0xff").unwrap(), U256::from(255u8));
+        assert_eq!(U256::from_str_radix("zz", 36).unwrap(), U256::from(36u16 * 36 - 1));
+        assert!(U256::from_str("-1").is_err());
+        assert!(U256::from_str("").is_err());
+        assert!(U128::from_str(&(BigInt::one() << 128u32).to_string()).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::{self, Binary, Display, Formatter, LowerHex, Octal, UpperHex};
+
+use crate::uint::Uint;
+use crate::BigInt;
+
+impl<const LIMBS: usize> Uint<LIMBS> {
+    /// Converts a `Uint` into a string with the given base. Panics if `radix` is not in `2..=36`.
+    pub fn to_string_radix(&self, radix: u8) -> String {
+        BigInt::from(self).to_string_radix(radix)
+    }
+}
+
+impl<const LIMBS: usize> Display for Uint<LIMBS> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_radix(10))
+    }
+}
+
+impl<const LIMBS: usize> Binary for Uint<LIMBS> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_radix(2))
+    }
+}
+
+impl<const LIMBS: usize> Octal for Uint<LIMBS> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_radix(8))
+    }
+}
+
+impl<const LIMBS: usize> LowerHex for Uint<LIMBS> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_radix(16))
+    }
+}
+
+impl<const LIMBS: usize> UpperHex for Uint<LIMBS> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_string_radix(16).to_uppercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::uint::{U128, U256};
+
+    macro_rules! test_fmts {
+        ($name:ident: $a:expr, $dec:literal, $bin:expr, $oct:expr, $hex:expr) => {
+            #[test]
+            fn $name() {
+                assert_eq!($a.to_string(), $dec, "failed decimal output");
+                assert_eq!(format!("{:b}", $a), $bin, "failed binary output");
+                assert_eq!(format!("{:o}", $a), $oct, "failed octal output");
+                assert_eq!(
+                    format!("{:x}", $a),
+                    $hex.to_lowercase(),
+                    "failed lower hex output"
+                );
+                assert_eq!(
+                    format!("{:X}", $a),
+                    $hex.to_uppercase(),
+                    "failed upper hex output"
+                );
+            }
+        };
+    }
+
+    test_fmts!(zero: U256::ZERO, "0", "0", "0", "0");
+
+    test_fmts!(ten: U256::from(10u8), "10", "1010", "12", "a");
+
+    test_fmts!(max: U128::MAX,
+        "340282366920938463463374607431768211455", "1".repeat(128), format!("3{}", "7".repeat(42)), "f".repeat(32)
+    );
+}
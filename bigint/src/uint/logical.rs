@@ -0,0 +1,202 @@
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, ShlAssign, Shr,
+    ShrAssign,
+};
+
+use crate::uint::Uint;
+use crate::Base;
+
+macro_rules! impl_uint_bitop {
+    ($op:ident, $op_fn:ident, $assign:ident, $assign_fn:ident, $sym:tt) => {
+        impl<const LIMBS: usize> $op for Uint<LIMBS> {
+            type Output = Self;
+
+            fn $op_fn(mut self, other: Self) -> Self::Output {
+                self.$assign_fn(other);
+                self
+            }
+        }
+
+        impl<const LIMBS: usize> $op<&Uint<LIMBS>> for Uint<LIMBS> {
+            type Output = Self;
+
+            fn $op_fn(self, other: &Self) -> Self::Output {
+                self.$op_fn(*other)
+            }
+        }
+
+        impl<const LIMBS: usize> $op<Uint<LIMBS>> for &Uint<LIMBS> {
+            type Output = Uint<LIMBS>;
+
+            fn $op_fn(self, other: Uint<LIMBS>) -> Self::Output {
+                (*self).$op_fn(other)
+            }
+        }
+
+        impl<const LIMBS: usize> $op<&Uint<LIMBS>> for &Uint<LIMBS> {
+            type Output = Uint<LIMBS>;
+
+            fn $op_fn(self, other: &Uint<LIMBS>) -> Self::Output {
+                (*self).$op_fn(*other)
+            }
+        }
+
+        impl<const LIMBS: usize> $assign for Uint<LIMBS> {
+            fn $assign_fn(&mut self, other: Self) {
+                for (a, b) in self.data.iter_mut().zip(other.data) {
+                    *a = *a $sym b;
+                }
+            }
+        }
+
+        impl<const LIMBS: usize> $assign<&Uint<LIMBS>> for Uint<LIMBS> {
+            fn $assign_fn(&mut self, other: &Self) {
+                self.$assign_fn(*other);
+            }
+        }
+    };
+}
+
+impl_uint_bitop!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_uint_bitop!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_uint_bitop!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl<const LIMBS: usize> Not for Uint<LIMBS> {
+    type Output = Self;
+
+    fn not(mut self) -> Self::Output {
+        for a in self.data.iter_mut() {
+            *a = !*a;
+        }
+        self
+    }
+}
+
+impl<const LIMBS: usize> Uint<LIMBS> {
+    // Shifts left by `len` bits, discarding any bits shifted past the top of the type.
+    fn shl_bits(&mut self, len: usize) {
+        let (limbs, bits) = (len / Base::BITS as usize, len as u32 % Base::BITS);
+        if limbs >= LIMBS {
+            *self = Self::ZERO;
+            return;
+        }
+
+        for i in (0..LIMBS).rev() {
+            let hi = if i >= limbs { self.data[i - limbs] } else { 0 };
+            let lo = if i > limbs { self.data[i - limbs - 1] } else { 0 };
+            self.data[i] = if bits == 0 {
+                hi
+            } else {
+                hi << bits | lo >> (Base::BITS - bits)
+            };
+        }
+    }
+
+    // Shifts right by `len` bits.
+    fn shr_bits(&mut self, len: usize) {
+        let (limbs, bits) = (len / Base::BITS as usize, len as u32 % Base::BITS);
+        if limbs >= LIMBS {
+            *self = Self::ZERO;
+            return;
+        }
+
+        for i in 0..LIMBS {
+            let lo = if i + limbs < LIMBS { self.data[i + limbs] } else { 0 };
+            let hi = if i + limbs + 1 < LIMBS { self.data[i + limbs + 1] } else { 0 };
+            self.data[i] = if bits == 0 {
+                lo
+            } else {
+                lo >> bits | hi << (Base::BITS - bits)
+            };
+        }
+    }
+}
+
+macro_rules! impl_primitive_shift {
+    ($($t:ty),*) => {
+        $(
+            impl<const LIMBS: usize> Shl<$t> for Uint<LIMBS> {
+                type Output = Self;
+
+                fn shl(mut self, len: $t) -> Self::Output {
+                    self <<= len;
+                    self
+                }
+            }
+
+            impl<const LIMBS: usize> ShlAssign<$t> for Uint<LIMBS> {
+                fn shl_assign(&mut self, len: $t) {
+                    self.shl_bits(usize::try_from(len).unwrap_or(usize::MAX));
+                }
+            }
+
+            impl<const LIMBS: usize> Shr<$t> for Uint<LIMBS> {
+                type Output = Self;
+
+                fn shr(mut self, len: $t) -> Self::Output {
+                    self >>= len;
+                    self
+                }
+            }
+
+            impl<const LIMBS: usize> ShrAssign<$t> for Uint<LIMBS> {
+                fn shr_assign(&mut self, len: $t) {
+                    self.shr_bits(usize::try_from(len).unwrap_or(usize::MAX));
+                }
+            }
+        )*
+    }
+}
+
+impl_primitive_shift!(u8, u16, u32, u64, u128, usize);
+
+#[cfg(test)]
+mod tests {
+    use crate::uint::{U128, U256};
+
+    // xorshift, so the operands are reproducible without a dependency on an rng
+    fn next(seed: &mut u64) -> u128 {
+        let mut x = 0;
+        for _ in 0..2 {
+            *seed ^= *seed << 13;
+            *seed ^= *seed >> 7;
+            *seed ^= *seed << 17;
+            x = x << 64 | *seed as u128;
+        }
+        x
+    }
+
+    #[test]
+    fn against_u128() {
+        let mut seed = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..1000 {
+            let (x, y) = (next(&mut seed), next(&mut seed));
+            let (a, b) = (U128::from(x), U128::from(y));
+            let s = (y % 128) as u32;
+
+            assert_eq!(a & b, x & y);
+            assert_eq!(a | b, x | y);
+            assert_eq!(a ^ b, x ^ y);
+            assert_eq!(!a, !x);
+            assert_eq!(a << s, x << s, "{x} << {s}");
+            assert_eq!(a >> s, x >> s, "{x} >> {s}");
+        }
+    }
+
+    #[test]
+    fn references() {
+        let (a, b) = (U256::from(0xf0f0_u16), U256::from(0x0ff0_u16));
+        assert_eq!(&a & b, a & b);
+        assert_eq!(a | &b, a | b);
+        assert_eq!(&a ^ &b, a ^ b);
+    }
+
+    #[test]
+    fn shift_past_width() {
+        assert_eq!(U256::MAX << 256u32, U256::ZERO);
+        assert_eq!(U256::MAX >> 256u32, U256::ZERO);
+        assert_eq!(U256::MAX << u128::MAX, U256::ZERO);
+        assert_eq!(U256::ONE << 255u32 >> 255u32, U256::ONE);
+        assert_eq!((U256::MAX << 1u8).count_ones(), 255);
+    }
+}
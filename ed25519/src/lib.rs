@@ -145,13 +145,9 @@ fn recover_x(y: &BigInt, sign: Sign) -> Option<BigInt> {
         }
     }
 
-    let mut x = x2.modpow(&BigInt::from((P.clone() + 3) / 8), &P);
-    if ((&x * &x) - &x2).mod_floor(&*P) != BigInt::zero() {
-        x = (&x * BigInt::from(2).modpow(&((P.clone() - 1) / 4), &P)).mod_floor(&P);
-    }
-    if (&x * &x - &x2).mod_floor(&P) != BigInt::zero() {
-        return None;
-    }
+    let x2 = bigint::BigInt::from_le_bytes(&x2.mod_floor(&P).to_bytes_le().1);
+    let p = bigint::BigInt::from_le_bytes(&P.to_bytes_le().1);
+    let mut x = BigInt::from_bytes_le(Sign::Plus, &x2.sqrt_mod(&p)?.to_le_bytes());
 
     if (x.iter_u32_digits().next().unwrap() & 1) as u8 != if let Sign::Minus = sign { 1 } else { 0 }
     {
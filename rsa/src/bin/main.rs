@@ -1,8 +1,19 @@
+use random::OsRng;
 use rsa::Rsa;
+use sha::sha256::Sha256;
 
 fn main() {
-    let keypair = Rsa::new();
+    let mut rng = OsRng::new();
+    let keypair = Rsa::generate(2048, &mut rng);
     let msg = "Hello, world!";
     println!("{:?}", keypair);
-    assert_eq!(String::from_utf8(keypair.decrypt(&keypair.encrypt(msg.as_bytes()))).unwrap(), msg);
-}
\ No newline at end of file
+
+    let ciphertext = keypair
+        .public_key()
+        .encrypt_oaep::<Sha256, _, _>(msg.as_bytes(), None, &mut rng)
+        .unwrap();
+    let plaintext = keypair
+        .decrypt_oaep::<Sha256, _, _>(&ciphertext, None, &mut rng)
+        .unwrap();
+    assert_eq!(String::from_utf8(plaintext).unwrap(), msg);
+}
@@ -1,105 +1,862 @@
-use random::{self, CpuRng, Rng};
+#![allow(incomplete_features)]
+#![feature(generic_const_exprs)]
+
+mod format;
+mod padding;
+mod public;
+
+pub use padding::DigestInfo;
+pub use public::RsaPublicKey;
+
+use std::error;
+use std::fmt::{self, Debug, Display, Formatter};
+
 use bigint::BigInt;
+use random::{Rng, RngOutput};
+use sha::{Digest, Sha};
+
+/// The public exponent used for generated keys, `2^16 + 1`.
+pub const PUBLIC_EXPONENT: u32 = 65537;
+
+/// An error generated by an RSA operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The message is too long to be encrypted with the key and padding.
+    MessageTooLong,
+    /// The input is not a valid ciphertext for the key and padding. The cause is deliberately not
+    /// given, and callers should not reveal any more than that decryption failed.
+    Decryption,
+    /// The key is too small to hold a signature with the chosen hash and salt length.
+    KeyTooSmall,
+    /// The signature is not valid for the message and key.
+    Verification,
+    /// The input is not a valid encoding of a key of the expected kind, or the parts of the key do
+    /// not agree with each other.
+    InvalidKey,
+    /// A private key operation gave a result which does not match its input, so it was discarded.
+    /// This points to a corrupted key or a hardware fault.
+    Fault,
+}
+
+impl error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::MessageTooLong => write!(f, "message too long"),
+            Error::Decryption => write!(f, "decryption error"),
+            Error::KeyTooSmall => write!(f, "key too small for the hash and salt length"),
+            Error::Verification => write!(f, "invalid signature"),
+            Error::InvalidKey => write!(f, "invalid key"),
+            Error::Fault => write!(f, "private key operation failed its consistency check"),
+        }
+    }
+}
 
-#[derive(Debug)]
+/// An RSA private key, holding the public modulus and exponent along with the private exponent,
+/// the prime factors of the modulus and the values used to speed up private key operations with
+/// the Chinese remainder theorem.
+#[derive(Clone, PartialEq, Eq)]
 pub struct Rsa {
-    modulus: (u16, u16),
-    private: BigInt,
-    public: BigInt,
+    public: RsaPublicKey,
+    d: BigInt,
+    p: BigInt,
+    q: BigInt,
+    // d mod (p - 1)
+    dp: BigInt,
+    // d mod (q - 1)
+    dq: BigInt,
+    // q^-1 mod p
+    qinv: BigInt,
 }
 
-const PRIME_RANGE: usize = u16::MAX as usize;
+impl Rsa {
+    /// Generates a key with a modulus of exactly `bits` bits, from two random primes of half the
+    /// size drawn from `rng`, and a public exponent of `PUBLIC_EXPONENT`. Panics if `bits` is less
+    /// than `64`.
+    ///
+    /// The private exponent is the inverse of `e` modulo `lcm(p - 1, q - 1)`, which is the
+    /// smallest one that works.
+    pub fn generate<R: Rng>(bits: usize, rng: &mut R) -> Self
+    where
+        u64: RngOutput<R>,
+    {
+        assert!(bits >= 64, "the modulus must have at least 64 bits");
+
+        let e = BigInt::from(PUBLIC_EXPONENT);
+        // `e` is prime, so it only shares a factor with `p - 1` if it divides it
+        let prime = |bits: usize, rng: &mut R| loop {
+            let p = BigInt::random_prime(bits, rng);
+            if (p.clone() - 1u8) % &e != 0 {
+                break p;
+            }
+        };
 
-fn primes() -> Vec<u16> {
-    let mut v = vec![true; PRIME_RANGE - 1];
-    let len = (PRIME_RANGE as f32).sqrt() as usize;
+        loop {
+            let p = prime(bits - bits / 2, rng);
+            let q = prime(bits / 2, rng);
 
-    for i in 2..len {
-        if v[i] {
-            let mut j = i.pow(2);
-            while j < PRIME_RANGE - 1 {
-                v[j] = false;
-                j += i;
+            // primes this close together would let the modulus be factored from its square root
+            if (&p - &q).abs().bits() + 100 <= bits / 2 {
+                continue;
             }
+
+            let (p, q) = if p > q { (p, q) } else { (q, p) };
+            return Self::from_primes(p, q, e);
         }
     }
 
-    v.iter().enumerate().skip(2).filter(|(_, &e)| e).map(|(i, _)| i as u16).collect()
-}
+    // Calculates the rest of the key from two distinct primes and a public exponent coprime to
+    // both of them less one.
+    fn from_primes(p: BigInt, q: BigInt, e: BigInt) -> Self {
+        let (p1, q1) = (p.clone() - 1u8, q.clone() - 1u8);
+        let lambda = p1.lcm(&q1);
+        let d = e.mod_inverse(&lambda).unwrap();
 
-// fn is_prime(x: u128) -> bool {
-//     println!("checking prime");
-//     for i in (3..((x as f32).sqrt() as u128)).step_by(2) {
-//         if x % i == 0 {
-//             return false;
-//         }
-//     }
-//     true
-// }
+        Self {
+            public: RsaPublicKey { n: &p * &q, e },
+            dp: &d % &p1,
+            dq: &d % &q1,
+            qinv: q.mod_inverse(&p).unwrap(),
+            d,
+            p,
+            q,
+        }
+    }
 
-// fn is_safe_prime(x: u128) -> bool {
-//     is_prime((x - 1) / 2)
-// }
+    /// Returns the public half of the key, which can be shared.
+    pub fn public_key(&self) -> &RsaPublicKey {
+        &self.public
+    }
 
-impl Rsa {
-    pub fn new() -> Self {
-        let mut gen = CpuRng::new();
-        let primes = primes().into_iter().filter(|e| (1023..).contains(e)).collect::<Vec<u16>>();
-        let modulus =  (
-            primes[gen.get::<usize>() % primes.len()],
-            primes[gen.get::<usize>() % primes.len()],
-        );
-        let m = BigInt::from(modulus.0) * modulus.1;
-        let other = (modulus.0 as u32 - 1) * (modulus.1 as u32 - 1);
-        println!("{:?}", modulus);
-
-        let public = BigInt::from(*primes.iter().filter(|&e| *e >= modulus.0 / 2).find(|&e| other % *e as u32 != 0).unwrap());
-        println!("{public}");
-        // let private = *primes.iter().filter(|&e| *e >= modulus.0 - 1).find(|&e| *e as u32 * public as u32 % other == 1).unwrap();
-        // let private = (public..m).find(|&e| e as u32 * public % other == 1).unwrap();
-        let mut private = BigInt::zero();
-        for i in public.clone()..m {
-            if &i * &public % other == BigInt::one() {
-                private = i;
-                break;
-            }
+    /// Returns the modulus `n`.
+    pub fn modulus(&self) -> &BigInt {
+        &self.public.n
+    }
+
+    /// Returns the public exponent `e`.
+    pub fn public_exponent(&self) -> &BigInt {
+        &self.public.e
+    }
+
+    /// Returns the private exponent `d`.
+    pub fn private_exponent(&self) -> &BigInt {
+        &self.d
+    }
+
+    /// Returns the prime factors `(p, q)` of the modulus. Generated keys have `p > q`, and loaded
+    /// ones keep the order they were stored in.
+    pub fn primes(&self) -> (&BigInt, &BigInt) {
+        (&self.p, &self.q)
+    }
+
+    /// Returns the CRT exponents and coefficient `(d mod (p - 1), d mod (q - 1), q^-1 mod p)`.
+    pub fn crt_params(&self) -> (&BigInt, &BigInt, &BigInt) {
+        (&self.dp, &self.dq, &self.qinv)
+    }
+
+    /// Returns the number of bits in the modulus.
+    pub fn bits(&self) -> usize {
+        self.public.bits()
+    }
+
+    fn size(&self) -> usize {
+        self.public.size()
+    }
+
+    /// Decrypts `bytes` with the private key and no padding, drawing the blinding value from
+    /// `rng`. Returns an error if the ciphertext is not below the modulus.
+    pub fn decrypt<R: Rng>(&self, bytes: &[u8], rng: &mut R) -> Result<Vec<u8>, Error>
+    where
+        u64: RngOutput<R>,
+    {
+        let c = BigInt::from_be_bytes(bytes);
+        if c >= self.public.n {
+            return Err(Error::Decryption);
         }
 
-        Self { modulus, private, public }
+        Ok(self.private_op(&c, rng)?.to_be_bytes())
+    }
+
+    /// Decrypts a ciphertext from `RsaPublicKey::encrypt_oaep` made with the same hash and label,
+    /// drawing the blinding value from `rng`.
+    pub fn decrypt_oaep<H: Sha<D>, D: Digest, R: Rng>(
+        &self,
+        ciphertext: &[u8],
+        label: Option<&[u8]>,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error>
+    where
+        [(); D::OUTPUT_SIZE]:,
+        u64: RngOutput<R>,
+    {
+        let em = self.private_op_bytes(ciphertext, rng)?;
+        padding::oaep_decode::<H, D>(&em, label.unwrap_or_default())
     }
 
-    pub fn encrypt(&self, bytes: &[u8]) -> Vec<u8> {
-        let m = BigInt::from(self.modulus.0) * self.modulus.1;
-        let d = BigInt::from_be_bytes(bytes);
-        assert!(d < m, "too much data for the size of the modulus");
+    /// Decrypts a ciphertext from `RsaPublicKey::encrypt_pkcs1v15`, drawing the blinding value
+    /// from `rng`.
+    ///
+    /// Telling anyone whether this failed lets them decrypt ciphertexts of their choosing, so
+    /// errors must not be reported back to whoever sent the ciphertext.
+    pub fn decrypt_pkcs1v15<R: Rng>(&self, ciphertext: &[u8], rng: &mut R) -> Result<Vec<u8>, Error>
+    where
+        u64: RngOutput<R>,
+    {
+        let em = self.private_op_bytes(ciphertext, rng)?;
+        padding::pkcs1v15_encrypt_decode(&em)
+    }
 
-        d.modpow(&self.public, &m).to_be_bytes()
+    /// Signs the hash of `msg` with RSASSA-PKCS1-v1_5, using `H` and naming it in a DigestInfo,
+    /// drawing the blinding value from `rng`.
+    pub fn sign_pkcs1v15<H: Sha<D> + DigestInfo, D: Digest, R: Rng>(
+        &self,
+        msg: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error>
+    where
+        [(); D::OUTPUT_SIZE]:,
+        u64: RngOutput<R>,
+    {
+        let em = padding::pkcs1v15_sign_encode::<H, D>(msg, self.size())?;
+        self.sign_op(&em, rng)
     }
 
-    pub fn decrypt(&self, bytes: &[u8]) -> Vec<u8> {
-        let m = BigInt::from(self.modulus.0) * self.modulus.1;
-        let d = BigInt::from_be_bytes(bytes);
-        assert!(d < m, "too much data for the size of the modulus");
+    /// Signs the hash of `msg` with RSASSA-PSS, using `H` for both the message hash and MGF1,
+    /// and a random salt of `salt_len` bytes drawn from `rng`, which also provides the blinding
+    /// value. The usual salt length is the size of the hash.
+    pub fn sign_pss<H: Sha<D>, D: Digest, R: Rng>(
+        &self,
+        msg: &[u8],
+        salt_len: usize,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error>
+    where
+        [(); D::OUTPUT_SIZE]:,
+        u64: RngOutput<R>,
+    {
+        let em = padding::pss_encode::<H, D, R>(msg, self.bits() - 1, salt_len, rng)?;
+        self.sign_op(&em, rng)
+    }
 
-        d.modpow(&self.private, &m).to_be_bytes()
+    // Applies the private key to an encoded block, which is below the modulus, giving a signature
+    // the size of the modulus.
+    fn sign_op<R: Rng>(&self, em: &[u8], rng: &mut R) -> Result<Vec<u8>, Error>
+    where
+        u64: RngOutput<R>,
+    {
+        let s = self.private_op(&BigInt::from_be_bytes(em), rng)?;
+        Ok(s.to_be_bytes_padded(self.size()).unwrap())
+    }
+
+    // Applies the private key to a ciphertext, which must be exactly the size of the modulus,
+    // giving the encoded block.
+    fn private_op_bytes<R: Rng>(&self, ciphertext: &[u8], rng: &mut R) -> Result<Vec<u8>, Error>
+    where
+        u64: RngOutput<R>,
+    {
+        let c = BigInt::from_be_bytes(ciphertext);
+        if ciphertext.len() != self.size() || c >= self.public.n {
+            return Err(Error::Decryption);
+        }
+
+        Ok(self
+            .private_op(&c, rng)?
+            .to_be_bytes_padded(self.size())
+            .unwrap())
+    }
+
+    // Calculates `c^d mod n` for `c < n`.
+    //
+    // The exponentiation is split into constant-time ones modulo `p` and `q` with the Chinese
+    // remainder theorem, then recombined with Garner's formula, which is about four times faster
+    // than working modulo `n`. The input is blinded as `c * r^e` for a random `r`, so the values
+    // being exponentiated are unrelated to `c`, and the result is checked by raising it back to
+    // `e`, so a fault in one of the halves can never release a value which reveals a factor of
+    // `n`.
+    fn private_op<R: Rng>(&self, c: &BigInt, rng: &mut R) -> Result<BigInt, Error>
+    where
+        u64: RngOutput<R>,
+    {
+        let (r, r_inv) = loop {
+            let r = BigInt::random_range(BigInt::one()..self.public.n.clone(), rng);
+            if let Some(r_inv) = r.mod_inverse(&self.public.n) {
+                break (r, r_inv);
+            }
+        };
+        let blinded = c * &r.modpow(&self.public.e, &self.public.n) % &self.public.n;
+
+        let m1 = blinded.modpow_ct(&self.dp, &self.p);
+        let m2 = blinded.modpow_ct(&self.dq, &self.q);
+        let h = (&self.qinv * (m1 - &m2)).modulo(&self.p);
+        let m = (m2 + h * &self.q) * r_inv % &self.public.n;
+
+        if m.modpow(&self.public.e, &self.public.n) != *c {
+            return Err(Error::Fault);
+        }
+        Ok(m)
+    }
+}
+
+/// Only the public parts of the key are shown.
+impl Debug for Rsa {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Rsa")
+            .field("n", &self.public.n)
+            .field("e", &self.public.e)
+            .finish_non_exhaustive()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use mersenne_twister::MersenneTwister;
+    use sha::sha1::Sha1;
+    use sha::sha256::Sha256;
+    use sha::sha512::Sha512;
 
     #[test]
     fn test() {
         let msg = &b"Hi";
-        let keypair = Rsa::new();
-        println!("{:?}", keypair);
-        println!("{:?}", msg);
-        println!("{:?}", keypair.encrypt(&msg[..]));
-        // println!("{:?}", msg.as_bytes());
-        // println!("{:?}", keypair.encrypt(msg.as_bytes()));
-        // println!("{:?}", keypair.decrypt(&keypair.encrypt(msg.as_bytes())));
-        // assert_eq!(String::from_utf8(keypair.decrypt(&keypair.encrypt(msg.as_bytes()))).unwrap(), msg);
-        assert_eq!(keypair.decrypt(&keypair.encrypt(&msg[..])), msg.to_vec());
+        let mut rng = MersenneTwister::from(1);
+        let keypair = Rsa::generate(512, &mut rng);
+        assert_eq!(
+            keypair.decrypt(&keypair.public_key().encrypt(&msg[..]), &mut rng),
+            Ok(msg.to_vec())
+        );
+    }
+
+    fn check(key: &Rsa, bits: usize) {
+        let (p, q) = key.primes();
+        let (p1, q1) = (p.clone() - 1u8, q.clone() - 1u8);
+
+        assert_eq!(key.bits(), bits);
+        assert_eq!(key.modulus(), &(p * q));
+        assert_eq!(key.public_exponent(), &BigInt::from(PUBLIC_EXPONENT));
+        assert!(p > q);
+        assert!(p.is_probable_prime_bpsw() && q.is_probable_prime_bpsw());
+
+        // e * d = 1 (mod lambda(n)), with d < lambda(n)
+        let lambda = p1.lcm(&q1);
+        let d = key.private_exponent();
+        assert_eq!(key.public_exponent() * d % &lambda, 1);
+        assert!(d < &lambda);
+
+        let (dp, dq, qinv) = key.crt_params();
+        assert_eq!(dp, &(d % &p1));
+        assert_eq!(dq, &(d % &q1));
+        assert_eq!(qinv * q % p, 1);
+    }
+
+    #[test]
+    fn generate() {
+        for bits in [64, 65, 127, 512, 1000] {
+            let key = Rsa::generate(bits, &mut MersenneTwister::from(bits as u64));
+            check(&key, bits);
+        }
+    }
+
+    #[test]
+    fn generate_2048() {
+        let key = Rsa::generate(2048, &mut MersenneTwister::from(0x5eed));
+        check(&key, 2048);
+
+        let m = BigInt::from(0x1234_5678u32).pow(60u8);
+        let c = m.modpow(key.public_exponent(), key.modulus());
+        assert_eq!(c.modpow(key.private_exponent(), key.modulus()), m);
+    }
+
+    #[test]
+    fn reproducible() {
+        let a = Rsa::generate(256, &mut MersenneTwister::from(42));
+        let b = Rsa::generate(256, &mut MersenneTwister::from(42));
+        let c = Rsa::generate(256, &mut MersenneTwister::from(43));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn debug_hides_private_key() {
+        let key = Rsa::generate(64, &mut MersenneTwister::from(7));
+        let debug = format!("{key:?}");
+        assert!(debug.contains(&format!("{:?}", key.modulus())));
+        assert!(!debug.contains(&format!("{:?}", key.private_exponent())));
+    }
+
+    #[test]
+    fn decrypt_crt() {
+        let mut rng = MersenneTwister::from(3);
+        let key = Rsa::generate(768, &mut rng);
+
+        for i in 0..20u32 {
+            let c = BigInt::random_below(key.modulus(), &mut rng) + i;
+            let c = c % key.modulus();
+            let m = c.modpow(key.private_exponent(), key.modulus());
+            assert_eq!(key.private_op(&c, &mut rng), Ok(m));
+        }
+        assert_eq!(
+            key.private_op(&BigInt::zero(), &mut rng),
+            Ok(BigInt::zero())
+        );
+        assert_eq!(key.private_op(&BigInt::one(), &mut rng), Ok(BigInt::one()));
+    }
+
+    #[test]
+    fn decrypt_too_large() {
+        let mut rng = MersenneTwister::from(4);
+        let key = Rsa::generate(128, &mut rng);
+        let c = key.modulus().to_be_bytes();
+        assert_eq!(key.decrypt(&c, &mut rng), Err(Error::Decryption));
+    }
+
+    #[test]
+    fn fault() {
+        let mut rng = MersenneTwister::from(5);
+        let mut key = Rsa::generate(256, &mut rng);
+        key.dq += 1u8;
+
+        let c = BigInt::from(0x1234_5678u32);
+        assert_eq!(key.private_op(&c, &mut rng), Err(Error::Fault));
+        assert_eq!(key.decrypt(&c.to_be_bytes(), &mut rng), Err(Error::Fault));
+    }
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // A 2048-bit key generated by OpenSSL, with ciphertexts it made for `OPENSSL_MSG`.
+    fn openssl_key() -> Rsa {
+        let p = concat!(
+            "d940ade2f68fcec18ffe74c423350a7534eb66714121e4b5cf23e9bdf900d5fb2ef3a04975bc94889b7bb82c",
+            "19534cee10c044ffe40bac0498317dd8ba8d58ff61711d938c82c78aab628e15c662525381956e08b9b50a62",
+            "623acb4f8dff76d5ab37f0e2e566c0c15a270b9296a78678c7839e14dcd41e881f21bf0478ebe18f",
+        );
+        let q = concat!(
+            "b610e5268e4c080184731ef3db8cc229d566e33b30b5f2f73c1e73a276ea9b178a3420e8d044993ce1a732f0",
+            "91f897933e2513d1c4fff714169cf43849805e245b978d7a406fa88397d17b8d829181d14a7a73759e40888f",
+            "cdeb528b067e11dba909168d7062a58f72a2b65432db33bf15083afe686ae209410e2fba1e5cc2d7",
+        );
+        Rsa::from_primes(
+            BigInt::from_str_radix(p, 16).unwrap(),
+            BigInt::from_str_radix(q, 16).unwrap(),
+            BigInt::from(PUBLIC_EXPONENT),
+        )
+    }
+
+    const OPENSSL_MSG: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    #[test]
+    fn decrypt_openssl() {
+        let key = openssl_key();
+        let mut rng = MersenneTwister::from(6);
+
+        let c = hex(concat!(
+                "80569ec69307633af01a8a24be5cf20790d268cb83931468ce8b832c9597ded6f9316c8c0c57cfad5c06c2a1",
+                "d56f57200e339ca0f91195ebbb9e41bf59add082326620a88f34c3bd7ad102d2d7c383b385e90a77fbc0664f",
+                "13826a6da66dfd2a086b9bc9a08c01a31e9694828ad0575b46df214950f4b590e1d3b6031da0f2d3db8d2edd",
+                "2cccf1f5f7f2533fb6cac716952b429e6e2a97b6e421eacefeb45165eb6e347c34a03d7da35c0c3ab08a7448",
+                "c28df18452c36adcb69cac1e57104e9f919da7a489ecc17da46b172c6dc009bd323357010b5d213bf7c87aad",
+                "28f01089c7b83ec75a73cd44638de32af85610e7f1f3978221fc9f16b18c520890fb0601",
+            ));
+        assert_eq!(
+            key.decrypt_oaep::<Sha1, _, _>(&c, None, &mut rng),
+            Ok(OPENSSL_MSG.to_vec())
+        );
+
+        let c = hex(concat!(
+                "54667a00624f157c319335da0b02b57f70ec93165c9b2c805a1cb0d63a95c65621723347cc6865fa80f8a926",
+                "2ebac48507b79743079e4f3c69f08687a9473a215b6dcd2f21400b59a7e081d87c888b070243e3bf349519f3",
+                "1111d2b60d8f31fa5e814026070480ca83fb74d3b61aae59803fdd14c69121f3fef4d3dae98ff2d39175414a",
+                "bcd0ab140dd5c2d05978b7b99fd5a1f8ce7361b41736fb4f0ade0f0d04af2b26ad101fd6b6fa944b4739ee49",
+                "dc5230cb30615c91db806ba297d77cdd8a839688eb669324973ca1456a5bd6826b015bd6152161f40451df48",
+                "8c72154c8660b67ab7761a4ab94a4abc80243dadf6f0b2c7aa7a65d4c0da0278698d40a3",
+            ));
+        assert_eq!(
+            key.decrypt_oaep::<Sha256, _, _>(&c, Some(b"label"), &mut rng),
+            Ok(OPENSSL_MSG.to_vec())
+        );
+        assert_eq!(
+            key.decrypt_oaep::<Sha256, _, _>(&c, None, &mut rng),
+            Err(Error::Decryption)
+        );
+
+        let c = hex(concat!(
+                "35f1f490da820a17dd6cf700359b3db17686e9da9f4cfeea08f7ea92387f09742568593b6118f44bcc665370",
+                "0afb1c53848630a5feefe58cd5f82972653d96c6759bbe380368b3a14c504e19c845d9086a3112bc61bd6f10",
+                "a62bb15a000e36f515859b94fc3213852a8bcf184ac539a5fb2839d20a8ff2aed6c51afecda2f81123ac82aa",
+                "71f6839c97f5df97bebed294f7441d6a5b27dfe8ef37e375a3c65b3375146baa074204859a88cb28c8d5bc38",
+                "1c8dc849d847a225fa18d14261a82adb65e38d4f812c528f8cf05818e4c977d64e17794c67b7c0b80230fa54",
+                "7e4478de1dbf86cd26bdc3f085d78212ac204cab3233889d98603b8efaccd88491cf287a",
+            ));
+        assert_eq!(
+            key.decrypt_oaep::<Sha512, _, _>(&c, None, &mut rng),
+            Ok(OPENSSL_MSG.to_vec())
+        );
+
+        let c = hex(concat!(
+                "0eedec5d18a1b9622f1486437bc834dc900fab5b1cacf8a96144e67f34b936cb3ff37ba6a39b6daa24b06027",
+                "373c7d41cad9495b8170548d6daf1bf18501bdae29cdb80c06fbee3cd39d94b925563eb7832194bfcfd22561",
+                "eca0a5fb3fd2e0636e48f8b41f8d949e97feab92027c152b25d1b7eb70f91daaae997477ea903d21d53f6f05",
+                "e56ce52adb9415a112a2937b6ebf45589d4de0cc010cbe2b8869a798907cf9939ece355cb430b1273fdede87",
+                "ae090cb2131e339a2f1625a7f4feac65c0c27efdf2ad7966a6a736d383b0a29fec75df9cadecf833489216b1",
+                "a8ca745613c76416bdac4b9c1bfcaae8d4e56fd424c9b66f65ed11c8eca9a13192c656b4",
+            ));
+        assert_eq!(key.decrypt_pkcs1v15(&c, &mut rng), Ok(OPENSSL_MSG.to_vec()));
+        assert_eq!(
+            key.decrypt_oaep::<Sha1, _, _>(&c, None, &mut rng),
+            Err(Error::Decryption)
+        );
+    }
+
+    // Example 1 from the RSAES-OAEP test vectors published with PKCS #1 v2.1 (RFC 8017), which
+    // use SHA-1 and an empty label.
+    #[test]
+    fn oaep_rfc8017() {
+        let n = concat!(
+            "a8b3b284af8eb50b387034a860f146c4919f318763cd6c5598c8ae4811a1e0abc4c7e0b082d693a5e7fced67",
+            "5cf4668512772c0cbc64a742c6c630f533c8cc72f62ae833c40bf25842e984bb78bdbf97c0107d55bdb662f5",
+            "c4e0fab9845cb5148ef7392dd3aaff93ae1e6b667bb3d4247616d4f5ba10d4cfd226de88d39f16fb",
+        );
+        let p = concat!(
+            "d32737e7267ffe1341b2d5c0d150a81b586fb3132bed2f8d5262864a9cb9f30af38be448598d413a172efb80",
+            "2c21acf1c11c520c2f26a471dcad212eac7ca39d",
+        );
+        let q = concat!(
+            "cc8853d1d54da630fac004f471f281c7b8982d8224a490edbeb33d3e3d5cc93c4765703d1dd791642f1f116a",
+            "0dd852be2419b2af72bfe9a030e860b0288b5d77",
+        );
+        let key = Rsa::from_primes(
+            BigInt::from_str_radix(p, 16).unwrap(),
+            BigInt::from_str_radix(q, 16).unwrap(),
+            BigInt::from(PUBLIC_EXPONENT),
+        );
+        assert_eq!(key.modulus(), &BigInt::from_str_radix(n, 16).unwrap());
+        let mut rng = MersenneTwister::from(7);
+
+        let examples = [
+            (
+                "6628194e12073db03ba94cda9ef9532397d50dba79b987004afefe34",
+                "18b776ea21069d69776a33e96bad48e1dda0a5ef",
+                concat!(
+                    "354fe67b4a126d5d35fe36c777791a3f7ba13def484e2d3908aff722fad468fb21696de95d0be911c2d3",
+                    "174f8afcc201035f7b6d8e69402de5451618c21a535fa9d7bfc5b8dd9fc243f8cf927db31322d6e881ea",
+                    "a91a996170e657a05a266426d98c88003f8477c1227094a0d9fa1e8c4024309ce1ecccb5210035d47ac7",
+                    "2e8a",
+                ),
+            ),
+            (
+                "750c4047f547e8e41411856523298ac9bae245efaf1397fbe56f9dd5",
+                "0cc742ce4a9b7f32f951bcb251efd925fe4fe35f",
+                concat!(
+                    "640db1acc58e0568fe5407e5f9b701dff8c3c91e716c536fc7fcec6cb5b71c1165988d4a279e1577d730",
+                    "fc7a29932e3f00c81515236d8d8e31017a7a09df4352d904cdeb79aa583adcc31ea698a4c05283daba90",
+                    "89be5491f67c1a4ee48dc74bbbe6643aef846679b4cb395a352d5ed115912df696ffe0702932946d7149",
+                    "2b44",
+                ),
+            ),
+        ];
+
+        for (msg, seed, c) in examples {
+            let (msg, seed, c) = (hex(msg), hex(seed), hex(c));
+
+            let em = padding::oaep_encode::<Sha1, _>(&msg, b"", &seed, 128).unwrap();
+            assert_eq!(key.public_key().public_op(&em), c);
+            assert_eq!(key.private_op_bytes(&c, &mut rng), Ok(em));
+            assert_eq!(key.decrypt_oaep::<Sha1, _, _>(&c, None, &mut rng), Ok(msg));
+        }
+    }
+
+    #[test]
+    fn oaep() {
+        let mut rng = MersenneTwister::from(8);
+        let key = Rsa::generate(1024, &mut rng);
+
+        for msg in [&b""[..], b"x", &[0; 62], &[0xff; 62]] {
+            let c = key
+                .public_key()
+                .encrypt_oaep::<Sha256, _, _>(msg, None, &mut rng)
+                .unwrap();
+            assert_eq!(c.len(), 128);
+            assert_eq!(
+                key.decrypt_oaep::<Sha256, _, _>(&c, None, &mut rng),
+                Ok(msg.to_vec())
+            );
+        }
+        assert_eq!(
+            key.public_key()
+                .encrypt_oaep::<Sha256, _, _>(&[0; 63], None, &mut rng),
+            Err(Error::MessageTooLong)
+        );
+        assert_eq!(
+            key.public_key()
+                .encrypt_oaep::<Sha512, _, _>(b"", None, &mut rng),
+            Err(Error::MessageTooLong)
+        );
+
+        // randomized, and bound to the hash and label
+        let a = key
+            .public_key()
+            .encrypt_oaep::<Sha1, _, _>(b"msg", Some(b"a"), &mut rng)
+            .unwrap();
+        let b = key
+            .public_key()
+            .encrypt_oaep::<Sha1, _, _>(b"msg", Some(b"a"), &mut rng)
+            .unwrap();
+        assert_ne!(a, b);
+        assert_eq!(
+            key.decrypt_oaep::<Sha1, _, _>(&b, Some(b"a"), &mut rng),
+            Ok(b"msg".to_vec())
+        );
+        assert_eq!(
+            key.decrypt_oaep::<Sha1, _, _>(&a, Some(b"b"), &mut rng),
+            Err(Error::Decryption)
+        );
+        assert_eq!(
+            key.decrypt_oaep::<Sha256, _, _>(&a, Some(b"a"), &mut rng),
+            Err(Error::Decryption)
+        );
+    }
+
+    #[test]
+    fn pkcs1v15() {
+        let mut rng = MersenneTwister::from(9);
+        let key = Rsa::generate(512, &mut rng);
+
+        for msg in [&b""[..], b"x", &[0; 53]] {
+            let c = key.public_key().encrypt_pkcs1v15(msg, &mut rng).unwrap();
+            assert_eq!(c.len(), 64);
+            assert_eq!(key.decrypt_pkcs1v15(&c, &mut rng), Ok(msg.to_vec()));
+        }
+        assert_eq!(
+            key.public_key().encrypt_pkcs1v15(&[0; 54], &mut rng),
+            Err(Error::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn ciphertext_size() {
+        let mut rng = MersenneTwister::from(10);
+        let key = Rsa::generate(512, &mut rng);
+        let c = key.public_key().encrypt_pkcs1v15(b"msg", &mut rng).unwrap();
+
+        // ciphertexts must be exactly the size of the modulus, and below it
+        assert_eq!(
+            key.decrypt_pkcs1v15(&c[1..], &mut rng),
+            Err(Error::Decryption)
+        );
+        let mut long = vec![0];
+        long.extend_from_slice(&c);
+        assert_eq!(
+            key.decrypt_pkcs1v15(&long, &mut rng),
+            Err(Error::Decryption)
+        );
+        assert_eq!(
+            key.decrypt_pkcs1v15(&[0xff; 64], &mut rng),
+            Err(Error::Decryption)
+        );
+    }
+
+    #[test]
+    fn verify_openssl() {
+        let key = openssl_key();
+        let mut rng = MersenneTwister::from(12);
+
+        // PKCS#1 v1.5 signatures are deterministic, so they should match exactly
+        let sig = hex(concat!(
+                "84ced71044491fd2d7b41b3e0aa9f917c80905ec92e651035d68b1b2069ea61beafa0a9199af73970b869560",
+                "8a2de00f51fcf4f4e4a6bd28e3f3bc356e3c57ddae45ac08ae8bcc01a9181c4f8b210413bb6fb138ac426c27",
+                "f1f461dbfea2e4ce83f140bb5d68aace80ccd9395d3b0ae148c8e57bd570aa54a34ee3e5610f2f4dfacd9075",
+                "b891e6d9e387ec38bdf1dfff17584e74842a4218ef33ac2caf45a874243bc2c631b6cf35925ee407d5a9505c",
+                "c7ebad7bcf5fdad96474e20a72abb5dead2ee45b8fe20387c7598e9f317e72348c32f245c9988e7a9972c238",
+                "bdb04ee24632c78402e52e63f4ecb6e7f0f67d3b7c0c9599e2aa9cbba31baf3f6157a47d",
+            ));
+        assert_eq!(
+            key.public_key()
+                .verify_pkcs1v15::<Sha1, _>(OPENSSL_MSG, &sig),
+            Ok(())
+        );
+        assert_eq!(
+            key.sign_pkcs1v15::<Sha1, _, _>(OPENSSL_MSG, &mut rng),
+            Ok(sig)
+        );
+
+        let sig = hex(concat!(
+                "7df0e7d7a902d479dc0b5201f198b7082ba747ba1c3d4e565b0f75327e8b74262a4f727fc0d2a82e757a465d",
+                "d3ba75db92cc6bfd1a7f2d7ec364198b6361f447f4b565352f087f3d1ee022d682f42334b9d16a3ea7e7af5a",
+                "3e05ec2e4c570114d4a6047b2f7901ca0b641f075ab92e546f05b0690d72b228d7283c38595c3a1ec974de27",
+                "c13833513e6c1cd9dfb975faf1e3a84fd47e35f98a475453d9a06863dda5150de824dfc66f7043a1b11b2ec0",
+                "89ad42af7718ee650ea2d50a288b7df0648f3693d0597a716d9f09ebc4de48bdf91fb11b444406aa084ff96c",
+                "623fdfacebd2292fbd17e995b6cbacf3a0b482990de52dc1c82faa3c48e1cd6321c802e5",
+            ));
+        assert_eq!(
+            key.public_key()
+                .verify_pkcs1v15::<Sha256, _>(OPENSSL_MSG, &sig),
+            Ok(())
+        );
+        assert_eq!(
+            key.public_key()
+                .verify_pkcs1v15::<Sha1, _>(OPENSSL_MSG, &sig),
+            Err(Error::Verification)
+        );
+        assert_eq!(
+            key.sign_pkcs1v15::<Sha256, _, _>(OPENSSL_MSG, &mut rng),
+            Ok(sig)
+        );
+
+        let sig = hex(concat!(
+                "763d0c3335c707cf11102986e1f6763abe9c8130388a5ee01ada456696fdcc8e19e41587fce7fd4e6279f90b",
+                "cf38967283cb9ee63ae41642a29f8acfbfe5d7d0b6aa5fdf577a6fee3c8110f74acede9731c9edc039585cb2",
+                "c2fac7080f145a855546a9e8e28a109be23a4bcda246963045480c492b7ffab5016501491f210584ad9be51f",
+                "271a8b00a54803746a357a2aee0e38f2607d15aa965d5665582513b48feb33d2d86679b7625b9e69fb0eee64",
+                "0b2c0c4e6df57e17711a87a78b2f6bebd098c3e5cba079d12a9a8e2a1244780a26742774a0990709c8f65ba9",
+                "9a7f571748ff86d0e1ad995bc48dcc47df3905fdae71f43501460ecb86258b0804b29339",
+            ));
+        assert_eq!(
+            key.public_key()
+                .verify_pkcs1v15::<Sha512, _>(OPENSSL_MSG, &sig),
+            Ok(())
+        );
+        assert_eq!(
+            key.sign_pkcs1v15::<Sha512, _, _>(OPENSSL_MSG, &mut rng),
+            Ok(sig)
+        );
+
+        let sig = hex(concat!(
+                "21ce5e06d9f3a4000f44f78118e80365343dddaf173cf2ade17a1a16fc2f0ae988501a12343bf2cad9adb67c",
+                "b910e500d072feef51092a34f05078edd66cf1e6d05601dbfbf7f50dcd2d2a7d25efea180fcb2780cc9704c9",
+                "3693478cab829bab9c8c3c9aa32143fc7c4b885f3199d6d9c946c621dfb8bcce87ae322f6c8c8cf2cbe9fdd1",
+                "713e2a3a3931d528ccad66dc36ce84425f32ad6abdd1e5df03fb56711238afe94851c33625524f2f53fbc315",
+                "b8c23610db23020c1606097ec534e7d69fdfe200139163d1d3a975b31aeeff33c993f69583b5aacd6b5b6768",
+                "fdff082c6fd3cf02444897868cf64e08b7475361d4062412d828ba69115de33edd50b6c8",
+            ));
+        assert_eq!(
+            key.public_key()
+                .verify_pss::<Sha1, _>(OPENSSL_MSG, &sig, Some(20)),
+            Ok(())
+        );
+        assert_eq!(
+            key.public_key()
+                .verify_pss::<Sha1, _>(OPENSSL_MSG, &sig, Some(32)),
+            Err(Error::Verification)
+        );
+
+        let sig = hex(concat!(
+                "7ac60361cc49c94cd58bf2921e402c056550774677c78004fff9432a998615e2183e5dec29d0f1f309741a8b",
+                "cbd637a8d682c426ac3756e4132a7d86041a3d6271daa16c8ab29cf059d307fdbf3289febf747df0bb40929c",
+                "7e4a7b49cbc8de4e6e226f1b13ede4bc246f057ae72a11901871e10ac86bd7c9b3a5a2e47b2d4d234a0c1472",
+                "a258cad3e8e6a7e8874781174244a8d7d1e21378640e20955588823214c473bc2db3183b85886398bf333202",
+                "ac4c52a870d5424087a4c0c222ef1776b4084a8dacf079fb556fc819fe8f585c26908320e17deab42a0dd06f",
+                "1e9590c0e3acaa6c73219469457e53309de3a449a292289727c7338b89a12a57d9738e23",
+            ));
+        assert_eq!(
+            key.public_key()
+                .verify_pss::<Sha256, _>(OPENSSL_MSG, &sig, Some(32)),
+            Ok(())
+        );
+        assert_eq!(
+            key.public_key()
+                .verify_pss::<Sha256, _>(OPENSSL_MSG, &sig, None),
+            Ok(())
+        );
+        assert_eq!(
+            key.public_key()
+                .verify_pss::<Sha256, _>(b"The quick brown fox", &sig, None),
+            Err(Error::Verification)
+        );
+
+        // the longest salt which fits
+        let sig = hex(concat!(
+                "986691e878a7aced3ae2dcbd962689ce800cdcf1315dfaca298e497bd434abbfe3f93918c22e764147314b9c",
+                "2a45516788984b1070c0cbdebed4e1ef9b3a7b4b473e32e90dec4b1f8eb3340d62053f07b65b2f8f1f2f0010",
+                "03f48d98e3b9a9649835dec469642478ed66c30f4877a0c15fd937ded3ac6aa12428bd8d42dd84feacc5f5d6",
+                "0ca85cec960aa92575ef2ebb95c158ab2d9bc93e98e292c8e3e5dabdb816700450403b361b980d91315a6e75",
+                "b0fe954e1c6beeb15e8c3a7fae517c52324fe33a20c1d69a51e4ae116c54218ccbf987edbc02b7873b5c5276",
+                "9fceb3445df5467d3cd2b7e100e810eff5c8ea50cb8a4dd4776762513b1848ca131b542c",
+            ));
+        assert_eq!(
+            key.public_key()
+                .verify_pss::<Sha512, _>(OPENSSL_MSG, &sig, Some(190)),
+            Ok(())
+        );
+        assert_eq!(
+            key.public_key()
+                .verify_pss::<Sha512, _>(OPENSSL_MSG, &sig, None),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn pss() {
+        let mut rng = MersenneTwister::from(13);
+
+        // a modulus one bit past a whole number of bytes makes the encoding a byte shorter
+        for bits in [1024, 1025] {
+            let key = Rsa::generate(bits, &mut rng);
+            for salt_len in [0, 32, 64] {
+                let sig = key
+                    .sign_pss::<Sha256, _, _>(b"msg", salt_len, &mut rng)
+                    .unwrap();
+                assert_eq!(sig.len(), key.size());
+                assert_eq!(
+                    key.public_key()
+                        .verify_pss::<Sha256, _>(b"msg", &sig, Some(salt_len)),
+                    Ok(())
+                );
+                assert_eq!(
+                    key.public_key().verify_pss::<Sha256, _>(b"msg", &sig, None),
+                    Ok(())
+                );
+                assert_eq!(
+                    key.public_key().verify_pss::<Sha512, _>(b"msg", &sig, None),
+                    Err(Error::Verification)
+                );
+            }
+
+            // salted, so signatures of the same message differ
+            let a = key.sign_pss::<Sha1, _, _>(b"msg", 20, &mut rng).unwrap();
+            let b = key.sign_pss::<Sha1, _, _>(b"msg", 20, &mut rng).unwrap();
+            assert_ne!(a, b);
+        }
+
+        let key = Rsa::generate(512, &mut rng);
+        assert_eq!(
+            key.sign_pss::<Sha512, _, _>(b"msg", 64, &mut rng),
+            Err(Error::KeyTooSmall)
+        );
+    }
+
+    #[test]
+    fn pkcs1v15_signature() {
+        let mut rng = MersenneTwister::from(14);
+        let key = Rsa::generate(1025, &mut rng);
+        let sig = key.sign_pkcs1v15::<Sha512, _, _>(b"msg", &mut rng).unwrap();
+        assert_eq!(sig.len(), 129);
+        assert_eq!(
+            key.public_key().verify_pkcs1v15::<Sha512, _>(b"msg", &sig),
+            Ok(())
+        );
+
+        let other = Rsa::generate(1025, &mut rng);
+        assert_eq!(
+            other
+                .public_key()
+                .verify_pkcs1v15::<Sha512, _>(b"msg", &sig),
+            Err(Error::Verification)
+        );
+        assert_eq!(
+            key.public_key()
+                .verify_pkcs1v15::<Sha512, _>(b"msg", &sig[1..]),
+            Err(Error::Verification)
+        );
+
+        let key = Rsa::generate(512, &mut rng);
+        assert_eq!(
+            key.sign_pkcs1v15::<Sha512, _, _>(b"msg", &mut rng),
+            Err(Error::KeyTooSmall)
+        );
     }
 }
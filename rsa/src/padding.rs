@@ -0,0 +1,507 @@
+//! Message encodings from [RFC 8017](https://www.rfc-editor.org/rfc/rfc8017), which turn a
+//! message into a block the size of the modulus before the RSA primitive is applied.
+//!
+//! Decoding works on the output of a private key operation, so it is written to take the same
+//! time whether or not the padding is valid, and to only reveal the outcome once every check has
+//! been made.
+
+use bigint::constant_time::{Choice, ConditionallySelectable, ConstantTimeEq};
+use random::{Rng, RngOutput};
+use sha::sha1::Sha1;
+use sha::sha256::Sha256;
+use sha::sha512::Sha512;
+use sha::{Digest, Sha};
+
+use crate::Error;
+
+// Whether two slices of the same length hold the same bytes.
+fn ct_eq_bytes(a: &[u8], b: &[u8]) -> Choice {
+    a.iter()
+        .zip(b)
+        .fold(Choice::TRUE, |acc, (x, y)| acc & x.ct_eq(y))
+}
+
+fn xor_in_place(a: &mut [u8], b: &[u8]) {
+    for (x, y) in a.iter_mut().zip(b) {
+        *x ^= y;
+    }
+}
+
+/// Draws `len` bytes from `rng`.
+pub(crate) fn random_bytes<R: Rng>(len: usize, rng: &mut R) -> Vec<u8>
+where
+    u64: RngOutput<R>,
+{
+    let mut bytes = Vec::with_capacity(len + 7);
+    while bytes.len() < len {
+        bytes.extend_from_slice(&rng.get::<u64>().to_le_bytes());
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+// Draws `len` bytes from `rng`, none of which are zero.
+fn random_nonzero_bytes<R: Rng>(len: usize, rng: &mut R) -> Vec<u8>
+where
+    u64: RngOutput<R>,
+{
+    let mut bytes = Vec::with_capacity(len);
+    while bytes.len() < len {
+        let x = rng.get::<u64>().to_le_bytes();
+        bytes.extend(x.into_iter().filter(|&b| b != 0).take(len - bytes.len()));
+    }
+    bytes
+}
+
+/// The mask generation function MGF1, which stretches `seed` into `len` bytes by hashing it
+/// followed by a 32-bit counter.
+pub(crate) fn mgf1<H: Sha<D>, D: Digest>(seed: &[u8], len: usize) -> Vec<u8>
+where
+    [(); D::OUTPUT_SIZE]:,
+{
+    let mut mask = Vec::with_capacity(len + H::OUTPUT_SIZE);
+    let mut counter = 0u32;
+    while mask.len() < len {
+        let mut hasher = H::new();
+        hasher.add(seed);
+        hasher.add(&counter.to_be_bytes());
+        mask.extend_from_slice(&hasher.digest().as_bytes());
+        counter += 1;
+    }
+    mask.truncate(len);
+    mask
+}
+
+/// Encodes `msg` into `k` bytes with EME-OAEP, masking it with `seed`, which must be
+/// `H::OUTPUT_SIZE` random bytes.
+pub(crate) fn oaep_encode<H: Sha<D>, D: Digest>(
+    msg: &[u8],
+    label: &[u8],
+    seed: &[u8],
+    k: usize,
+) -> Result<Vec<u8>, Error>
+where
+    [(); D::OUTPUT_SIZE]:,
+{
+    let h_len = H::OUTPUT_SIZE;
+    debug_assert_eq!(seed.len(), h_len);
+    if k < 2 * h_len + 2 || msg.len() > k - 2 * h_len - 2 {
+        return Err(Error::MessageTooLong);
+    }
+
+    // DB = lHash || PS || 0x01 || M
+    let mut db = Vec::with_capacity(k - h_len - 1);
+    db.extend_from_slice(&H::hash(label).as_bytes());
+    db.resize(k - msg.len() - h_len - 2, 0);
+    db.push(1);
+    db.extend_from_slice(msg);
+
+    xor_in_place(&mut db, &mgf1::<H, D>(seed, k - h_len - 1));
+    let mut masked_seed = seed.to_vec();
+    xor_in_place(&mut masked_seed, &mgf1::<H, D>(&db, h_len));
+
+    let mut em = vec![0];
+    em.extend_from_slice(&masked_seed);
+    em.extend_from_slice(&db);
+    Ok(em)
+}
+
+/// Recovers the message from a block encoded with `oaep_encode`.
+pub(crate) fn oaep_decode<H: Sha<D>, D: Digest>(em: &[u8], label: &[u8]) -> Result<Vec<u8>, Error>
+where
+    [(); D::OUTPUT_SIZE]:,
+{
+    let h_len = H::OUTPUT_SIZE;
+    // the length of the block only depends on the key
+    if em.len() < 2 * h_len + 2 {
+        return Err(Error::Decryption);
+    }
+
+    let (masked_seed, masked_db) = em[1..].split_at(h_len);
+    let mut seed = masked_seed.to_vec();
+    xor_in_place(&mut seed, &mgf1::<H, D>(masked_db, h_len));
+    let mut db = masked_db.to_vec();
+    xor_in_place(&mut db, &mgf1::<H, D>(&seed, masked_db.len()));
+
+    let mut valid = em[0].ct_eq(&0) & ct_eq_bytes(&db[..h_len], &H::hash(label).as_bytes());
+
+    // find the 0x01 after the zero padding without stopping early, and check nothing else is
+    // in the way
+    let mut searching = Choice::TRUE;
+    let mut start = 0;
+    for (i, b) in db.iter().enumerate().skip(h_len) {
+        let is_one = b.ct_eq(&1);
+        start.conditional_assign(&(i + 1), searching & is_one);
+        valid = valid & !(searching & !is_one & !b.ct_eq(&0));
+        searching = searching & !is_one;
+    }
+    valid = valid & !searching;
+
+    if bool::from(valid) {
+        Ok(db.split_off(start))
+    } else {
+        Err(Error::Decryption)
+    }
+}
+
+/// Encodes `msg` into `k` bytes with EME-PKCS1-v1_5, using nonzero padding bytes from `rng`.
+pub(crate) fn pkcs1v15_encrypt_encode<R: Rng>(
+    msg: &[u8],
+    k: usize,
+    rng: &mut R,
+) -> Result<Vec<u8>, Error>
+where
+    u64: RngOutput<R>,
+{
+    if k < 11 || msg.len() > k - 11 {
+        return Err(Error::MessageTooLong);
+    }
+
+    // EM = 0x00 || 0x02 || PS || 0x00 || M
+    let mut em = vec![0, 2];
+    em.append(&mut random_nonzero_bytes(k - msg.len() - 3, rng));
+    em.push(0);
+    em.extend_from_slice(msg);
+    Ok(em)
+}
+
+/// Recovers the message from a block encoded with `pkcs1v15_encrypt_encode`.
+pub(crate) fn pkcs1v15_encrypt_decode(em: &[u8]) -> Result<Vec<u8>, Error> {
+    if em.len() < 11 {
+        return Err(Error::Decryption);
+    }
+
+    let mut valid = em[0].ct_eq(&0) & em[1].ct_eq(&2);
+
+    // the separator is the first zero after the header
+    let mut searching = Choice::TRUE;
+    let mut start = 0;
+    for (i, b) in em.iter().enumerate().skip(2) {
+        let is_zero = b.ct_eq(&0);
+        start.conditional_assign(&(i + 1), searching & is_zero);
+        searching = searching & !is_zero;
+    }
+    // at least eight bytes of padding
+    valid = valid & !searching & !Choice::from(start < 11);
+
+    if bool::from(valid) {
+        Ok(em[start..].to_vec())
+    } else {
+        Err(Error::Decryption)
+    }
+}
+
+/// A hash which can be named in the DigestInfo of a PKCS#1 v1.5 signature.
+pub trait DigestInfo {
+    /// The DER encoding of a DigestInfo up to the digest itself, which holds the OID of the hash.
+    const DIGEST_INFO_PREFIX: &'static [u8];
+}
+
+// 1.3.14.3.2.26
+impl DigestInfo for Sha1 {
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x21, 0x30, 0x09, 0x06, 0x05, 0x2b, 0x0e, 0x03, 0x02, 0x1a, 0x05, 0x00, 0x04, 0x14,
+    ];
+}
+
+// 2.16.840.1.101.3.4.2.1
+impl DigestInfo for Sha256 {
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x01,
+        0x05, 0x00, 0x04, 0x20,
+    ];
+}
+
+// 2.16.840.1.101.3.4.2.3
+impl DigestInfo for Sha512 {
+    const DIGEST_INFO_PREFIX: &'static [u8] = &[
+        0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02, 0x03,
+        0x05, 0x00, 0x04, 0x40,
+    ];
+}
+
+/// Encodes the hash of `msg` into `k` bytes with EMSA-PKCS1-v1_5.
+pub(crate) fn pkcs1v15_sign_encode<H: Sha<D> + DigestInfo, D: Digest>(
+    msg: &[u8],
+    k: usize,
+) -> Result<Vec<u8>, Error>
+where
+    [(); D::OUTPUT_SIZE]:,
+{
+    // T = DigestInfo(H(M))
+    let mut t = H::DIGEST_INFO_PREFIX.to_vec();
+    t.extend_from_slice(&H::hash(msg).as_bytes());
+    if k < t.len() + 11 {
+        return Err(Error::KeyTooSmall);
+    }
+
+    // EM = 0x00 || 0x01 || PS || 0x00 || T
+    let mut em = vec![0, 1];
+    em.resize(k - t.len() - 1, 0xff);
+    em.push(0);
+    em.append(&mut t);
+    Ok(em)
+}
+
+/// Encodes the hash of `msg` into `ceil(em_bits / 8)` bytes with EMSA-PSS, with a random salt of
+/// `salt_len` bytes drawn from `rng`. The top bits beyond `em_bits` are left clear.
+pub(crate) fn pss_encode<H: Sha<D>, D: Digest, R: Rng>(
+    msg: &[u8],
+    em_bits: usize,
+    salt_len: usize,
+    rng: &mut R,
+) -> Result<Vec<u8>, Error>
+where
+    [(); D::OUTPUT_SIZE]:,
+    u64: RngOutput<R>,
+{
+    let h_len = H::OUTPUT_SIZE;
+    let em_len = (em_bits + 7) / 8;
+    if em_len < h_len + salt_len + 2 {
+        return Err(Error::KeyTooSmall);
+    }
+
+    let salt = random_bytes(salt_len, rng);
+    let h = pss_hash::<H, D>(&H::hash(msg).as_bytes(), &salt);
+
+    // DB = PS || 0x01 || salt
+    let mut db = vec![0; em_len - salt_len - h_len - 2];
+    db.push(1);
+    db.extend_from_slice(&salt);
+    xor_in_place(&mut db, &mgf1::<H, D>(&h, em_len - h_len - 1));
+    db[0] &= 0xff >> (8 * em_len - em_bits);
+
+    // EM = maskedDB || H || 0xbc
+    let mut em = db;
+    em.extend_from_slice(&h);
+    em.push(0xbc);
+    Ok(em)
+}
+
+/// Checks that `em` is an EMSA-PSS encoding of the hash of `msg` from `pss_encode`. With no
+/// `salt_len`, the salt may be of any length, which is found from the padding.
+pub(crate) fn pss_verify<H: Sha<D>, D: Digest>(
+    msg: &[u8],
+    em: &[u8],
+    em_bits: usize,
+    salt_len: Option<usize>,
+) -> Result<(), Error>
+where
+    [(); D::OUTPUT_SIZE]:,
+{
+    let h_len = H::OUTPUT_SIZE;
+    let em_len = (em_bits + 7) / 8;
+    let top_mask = 0xff >> (8 * em_len - em_bits);
+    if em.len() != em_len
+        || em_len < h_len + salt_len.unwrap_or(0) + 2
+        || em[em_len - 1] != 0xbc
+        || em[0] & !top_mask != 0
+    {
+        return Err(Error::Verification);
+    }
+
+    let (masked_db, h) = em[..em_len - 1].split_at(em_len - h_len - 1);
+    let mut db = masked_db.to_vec();
+    xor_in_place(&mut db, &mgf1::<H, D>(h, masked_db.len()));
+    db[0] &= top_mask;
+
+    // DB = PS || 0x01 || salt
+    let ps_len = db.iter().position(|&b| b != 0).ok_or(Error::Verification)?;
+    if db[ps_len] != 1 || salt_len.map_or(false, |len| db.len() - ps_len - 1 != len) {
+        return Err(Error::Verification);
+    }
+    let salt = &db[ps_len + 1..];
+
+    if pss_hash::<H, D>(&H::hash(msg).as_bytes(), salt) == h {
+        Ok(())
+    } else {
+        Err(Error::Verification)
+    }
+}
+
+// The hash of `M' = 0x00 00 00 00 00 00 00 00 || mHash || salt`, which the signature commits to.
+fn pss_hash<H: Sha<D>, D: Digest>(m_hash: &[u8], salt: &[u8]) -> Vec<u8>
+where
+    [(); D::OUTPUT_SIZE]:,
+{
+    let mut hasher = H::new();
+    hasher.add(&[0; 8]);
+    hasher.add(m_hash);
+    hasher.add(salt);
+    hasher.digest().as_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigint::BigInt;
+    use mersenne_twister::MersenneTwister;
+
+    #[test]
+    fn mgf1() {
+        // the first block is the hash of the seed and a zero counter
+        let mask = super::mgf1::<Sha256, _>(b"seed", 80);
+        assert_eq!(mask.len(), 80);
+        assert_eq!(mask[..32], Sha256::hash(b"seed\0\0\0\0").as_bytes());
+        assert_eq!(mask[64..], Sha256::hash(b"seed\0\0\0\x02").as_bytes()[..16]);
+        assert_eq!(super::mgf1::<Sha1, _>(b"seed", 0), []);
+    }
+
+    #[test]
+    fn oaep() {
+        let mut rng = MersenneTwister::from(1);
+        for k in [66, 100, 256] {
+            for len in [0, (k - 66) / 2, k - 66] {
+                let msg = random_bytes(len, &mut rng);
+                let seed = random_bytes(32, &mut rng);
+                let em = oaep_encode::<Sha256, _>(&msg, b"label", &seed, k).unwrap();
+                assert_eq!(em.len(), k);
+                assert_eq!(oaep_decode::<Sha256, _>(&em, b"label"), Ok(msg));
+                assert_eq!(oaep_decode::<Sha256, _>(&em, b""), Err(Error::Decryption));
+            }
+        }
+    }
+
+    #[test]
+    fn oaep_too_long() {
+        let seed = [0; 64];
+        assert_eq!(
+            oaep_encode::<Sha512, _>(b"", b"", &seed, 129),
+            Err(Error::MessageTooLong)
+        );
+        assert!(oaep_encode::<Sha512, _>(b"", b"", &seed, 130).is_ok());
+        assert_eq!(
+            oaep_encode::<Sha512, _>(&[1; 3], b"", &seed, 132),
+            Err(Error::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn oaep_corrupted() {
+        let seed = [7; 20];
+        let em = oaep_encode::<Sha1, _>(b"message", b"", &seed, 64).unwrap();
+
+        // a flipped bit anywhere changes the header, or unmasks the label hash differently
+        for i in 0..em.len() {
+            for bit in 0..8 {
+                let mut em = em.clone();
+                em[i] ^= 1 << bit;
+                assert_eq!(oaep_decode::<Sha1, _>(&em, b""), Err(Error::Decryption));
+            }
+        }
+        assert_eq!(
+            oaep_decode::<Sha1, _>(&em[..41], b""),
+            Err(Error::Decryption)
+        );
+    }
+
+    #[test]
+    fn pkcs1v15() {
+        let mut rng = MersenneTwister::from(2);
+        for k in [11, 64, 256] {
+            for len in [0, (k - 11) / 2, k - 11] {
+                let msg = random_bytes(len, &mut rng);
+                let em = pkcs1v15_encrypt_encode(&msg, k, &mut rng).unwrap();
+                assert_eq!(em.len(), k);
+                assert!(em[2..k - len - 1].iter().all(|&b| b != 0));
+                assert_eq!(pkcs1v15_encrypt_decode(&em), Ok(msg));
+            }
+        }
+        assert_eq!(
+            pkcs1v15_encrypt_encode(&[0; 54], 64, &mut rng),
+            Err(Error::MessageTooLong)
+        );
+    }
+
+    #[test]
+    fn pkcs1v15_invalid() {
+        let mut em = vec![0, 2];
+        em.extend_from_slice(&[0xff; 8]);
+        em.extend_from_slice(&[0, 1, 2, 3]);
+        assert_eq!(pkcs1v15_encrypt_decode(&em), Ok(vec![1, 2, 3]));
+
+        let mut e = em.clone();
+        e[0] = 1;
+        assert_eq!(pkcs1v15_encrypt_decode(&e), Err(Error::Decryption));
+        let mut e = em.clone();
+        e[1] = 1;
+        assert_eq!(pkcs1v15_encrypt_decode(&e), Err(Error::Decryption));
+        // padding shorter than eight bytes
+        let mut e = em.clone();
+        e[9] = 0;
+        assert_eq!(pkcs1v15_encrypt_decode(&e), Err(Error::Decryption));
+        // no separator
+        let mut e = em.clone();
+        e[10] = 0xff;
+        e.truncate(11);
+        assert_eq!(pkcs1v15_encrypt_decode(&e), Err(Error::Decryption));
+    }
+
+    #[test]
+    fn pkcs1v15_sign() {
+        let em = pkcs1v15_sign_encode::<Sha256, _>(b"abc", 64).unwrap();
+        assert_eq!(em[..2], [0, 1]);
+        assert!(em[2..12].iter().all(|&b| b == 0xff));
+        assert_eq!(em[12], 0);
+        assert_eq!(em[13..32], *Sha256::DIGEST_INFO_PREFIX);
+        assert_eq!(em[32..], Sha256::hash(b"abc").as_bytes());
+
+        // SHA-1 needs 35 bytes of DigestInfo and 11 of padding
+        assert!(pkcs1v15_sign_encode::<Sha1, _>(b"abc", 46).is_ok());
+        assert_eq!(
+            pkcs1v15_sign_encode::<Sha1, _>(b"abc", 45),
+            Err(Error::KeyTooSmall)
+        );
+    }
+
+    #[test]
+    fn pss() {
+        let mut rng = MersenneTwister::from(3);
+        for em_bits in [543, 544, 545, 1023] {
+            for salt_len in [0, 20, 32] {
+                let em = pss_encode::<Sha256, _, _>(b"msg", em_bits, salt_len, &mut rng).unwrap();
+                assert_eq!(em.len(), (em_bits + 7) / 8);
+                assert!(BigInt::from_be_bytes(&em).bits() <= em_bits);
+
+                assert_eq!(
+                    pss_verify::<Sha256, _>(b"msg", &em, em_bits, Some(salt_len)),
+                    Ok(())
+                );
+                assert_eq!(pss_verify::<Sha256, _>(b"msg", &em, em_bits, None), Ok(()));
+                assert_eq!(
+                    pss_verify::<Sha256, _>(b"msg", &em, em_bits, Some(salt_len + 1)),
+                    Err(Error::Verification)
+                );
+                assert_eq!(
+                    pss_verify::<Sha256, _>(b"msh", &em, em_bits, Some(salt_len)),
+                    Err(Error::Verification)
+                );
+            }
+        }
+        assert_eq!(
+            pss_encode::<Sha512, _, _>(b"msg", 8 * 129, 64, &mut rng),
+            Err(Error::KeyTooSmall)
+        );
+    }
+
+    #[test]
+    fn pss_corrupted() {
+        let mut rng = MersenneTwister::from(4);
+        let em = pss_encode::<Sha1, _, _>(b"msg", 767, 20, &mut rng).unwrap();
+
+        for i in 0..em.len() {
+            for bit in 0..8 {
+                let mut em = em.clone();
+                em[i] ^= 1 << bit;
+                assert_eq!(
+                    pss_verify::<Sha1, _>(b"msg", &em, 767, Some(20)),
+                    Err(Error::Verification)
+                );
+            }
+        }
+        assert_eq!(
+            pss_verify::<Sha1, _>(b"msg", &em[1..], 767, Some(20)),
+            Err(Error::Verification)
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! The public half of an RSA key, which can encrypt messages and verify signatures.
+
+use bigint::BigInt;
+use random::{Rng, RngOutput};
+use sha::{Digest, Sha};
+
+use crate::{padding, DigestInfo, Error};
+
+/// An RSA public key, made of the modulus and the public exponent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaPublicKey {
+    pub(crate) n: BigInt,
+    pub(crate) e: BigInt,
+}
+
+impl RsaPublicKey {
+    /// Returns the modulus `n`.
+    pub fn modulus(&self) -> &BigInt {
+        &self.n
+    }
+
+    /// Returns the public exponent `e`.
+    pub fn public_exponent(&self) -> &BigInt {
+        &self.e
+    }
+
+    /// Returns the number of bits in the modulus.
+    pub fn bits(&self) -> usize {
+        self.n.bits()
+    }
+
+    // The length of the modulus in bytes, which all ciphertexts and signatures are padded to.
+    pub(crate) fn size(&self) -> usize {
+        (self.bits() + 7) / 8
+    }
+
+    /// Encrypts `bytes` with the public key and no padding, which is deterministic and lets
+    /// ciphertexts be combined, so should only be used to build other schemes. Panics if `bytes`
+    /// is not below the modulus.
+    pub fn encrypt(&self, bytes: &[u8]) -> Vec<u8> {
+        let d = BigInt::from_be_bytes(bytes);
+        assert!(d < self.n, "too much data for the size of the modulus");
+
+        d.modpow(&self.e, &self.n).to_be_bytes()
+    }
+
+    /// Encrypts `msg` with RSAES-OAEP, using `H` for both the label hash and MGF1, and drawing
+    /// the seed from `rng`. A missing `label` is the same as an empty one. Returns an error if the
+    /// message is longer than the key size less twice the hash size and two bytes.
+    pub fn encrypt_oaep<H: Sha<D>, D: Digest, R: Rng>(
+        &self,
+        msg: &[u8],
+        label: Option<&[u8]>,
+        rng: &mut R,
+    ) -> Result<Vec<u8>, Error>
+    where
+        [(); D::OUTPUT_SIZE]:,
+        u64: RngOutput<R>,
+    {
+        let seed = padding::random_bytes(H::OUTPUT_SIZE, rng);
+        let em = padding::oaep_encode::<H, D>(msg, label.unwrap_or_default(), &seed, self.size())?;
+        Ok(self.public_op(&em))
+    }
+
+    /// Encrypts `msg` with RSAES-PKCS1-v1_5, drawing the padding from `rng`. Returns an error if
+    /// the message is longer than the key size less 11 bytes.
+    ///
+    /// This is only for compatibility, and `encrypt_oaep` should be preferred.
+    pub fn encrypt_pkcs1v15<R: Rng>(&self, msg: &[u8], rng: &mut R) -> Result<Vec<u8>, Error>
+    where
+        u64: RngOutput<R>,
+    {
+        let em = padding::pkcs1v15_encrypt_encode(msg, self.size(), rng)?;
+        Ok(self.public_op(&em))
+    }
+
+    /// Checks a signature of `msg` from `Rsa::sign_pkcs1v15` made with the same hash.
+    pub fn verify_pkcs1v15<H: Sha<D> + DigestInfo, D: Digest>(
+        &self,
+        msg: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error>
+    where
+        [(); D::OUTPUT_SIZE]:,
+    {
+        let em = self.verify_op(signature)?.to_be_bytes_padded(self.size());
+        match padding::pkcs1v15_sign_encode::<H, D>(msg, self.size()) {
+            Ok(expected) if em.as_ref() == Some(&expected) => Ok(()),
+            _ => Err(Error::Verification),
+        }
+    }
+
+    /// Checks a signature of `msg` from `Rsa::sign_pss` made with the same hash. If `salt_len` is
+    /// `None`, a salt of any length is accepted, as for signatures from other implementations
+    /// which use the longest salt that fits.
+    pub fn verify_pss<H: Sha<D>, D: Digest>(
+        &self,
+        msg: &[u8],
+        signature: &[u8],
+        salt_len: Option<usize>,
+    ) -> Result<(), Error>
+    where
+        [(); D::OUTPUT_SIZE]:,
+    {
+        let em_bits = self.bits() - 1;
+        let em = self
+            .verify_op(signature)?
+            .to_be_bytes_padded((em_bits + 7) / 8)
+            .ok_or(Error::Verification)?;
+        padding::pss_verify::<H, D>(msg, &em, em_bits, salt_len)
+    }
+
+    // Applies the public key to a signature, which must be exactly the size of the modulus and
+    // below it, giving the encoded block.
+    pub(crate) fn verify_op(&self, signature: &[u8]) -> Result<BigInt, Error> {
+        let s = BigInt::from_be_bytes(signature);
+        if signature.len() != self.size() || s >= self.n {
+            return Err(Error::Verification);
+        }
+
+        Ok(s.modpow(&self.e, &self.n))
+    }
+
+    // Applies the public key to an encoded block, giving bytes the size of the modulus.
+    pub(crate) fn public_op(&self, em: &[u8]) -> Vec<u8> {
+        BigInt::from_be_bytes(em)
+            .modpow(&self.e, &self.n)
+            .to_be_bytes_padded(self.size())
+            .unwrap()
+    }
+}